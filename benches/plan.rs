@@ -0,0 +1,57 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use neurotree::blackboard::BlackBoard;
+use neurotree::node::{Node, NodeResult};
+use neurotree::nodes::action::Action;
+use neurotree::nodes::inverter::Inverter;
+use neurotree::nodes::selector::Selector;
+use neurotree::nodes::sequence::Sequence;
+use neurotree::plan::ExecutionPlan;
+
+const TICKS: u32 = 20_000;
+
+fn counter(memory: &mut BlackBoard) -> NodeResult {
+    match memory.data.get_mut("ticks").and_then(|value| value.downcast_mut::<u64>()) {
+        Some(ticks) => {
+            *ticks += 1;
+            if *ticks % 3 == 0 { NodeResult::Failed } else { NodeResult::Passed }
+        },
+        None => NodeResult::Failed
+    }
+}
+
+// Alternating sequence/selector levels so that most leaves are visited on
+// each tick, with an inverter sprinkled in to exercise jump flipping.
+fn build(depth: usize) -> Box<dyn Node> {
+    if depth == 0 {
+        return Box::new(Action::new("leaf".into(), counter));
+    }
+    let children: Vec<Box<dyn Node>> = (0..4).map(|_| build(depth - 1)).collect();
+    match depth % 3 {
+        0 => Box::new(Inverter::new(Box::new(Sequence::new("sequence".into(), children)), "inverter".into())),
+        1 => Box::new(Selector::new("selector".into(), children)),
+        _ => Box::new(Sequence::new("sequence".into(), children))
+    }
+}
+
+fn measure(label: &str, mut tick: impl FnMut(&mut BlackBoard) -> NodeResult) {
+    let mut memory = BlackBoard::new();
+    memory.data.insert("ticks".into(), Box::new(0u64));
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        black_box(tick(&mut memory));
+    }
+    let elapsed = start.elapsed();
+    println!("{label:<10} {:>10.2?} total, {:>8.2?} per tick", elapsed, elapsed / TICKS);
+}
+
+fn main() {
+    let depth = 6;
+    let mut tree = build(depth);
+    let mut plan = ExecutionPlan::compile(build(depth));
+    println!("tree depth {depth}, {} leaves, {TICKS} ticks", plan.steps.len());
+
+    measure("recursive", |memory| tree.tick(memory));
+    measure("flattened", |memory| plan.tick(memory));
+}
@@ -0,0 +1,153 @@
+//! Ticking trees from async code, for actions that await IO such as a
+//! sensor read or a service call. Enabled with the `async` feature; it does
+//! not depend on any runtime.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeResult};
+
+pub type TickFuture<'a> = Pin<Box<dyn Future<Output = NodeResult> + Send + 'a>>;
+
+/// An async counterpart of `Node`. `tick` returns a boxed future so nodes
+/// can be stored as `Box<dyn AsyncNode>`; implement it with
+/// `Box::pin(async move { ... })`.
+pub trait AsyncNode: Send + Sync {
+    fn tick<'a>(&'a mut self, memory: &'a mut BlackBoard) -> TickFuture<'a>;
+    fn reset(&mut self);
+    fn get_name(&self) -> String;
+}
+
+/// Runs a synchronous `Node` as an `AsyncNode`. The tick completes without
+/// awaiting anything, so it should stay as cheap as it is in a sync tree.
+pub struct SyncNode {
+    pub node: Box<dyn Node>
+}
+
+impl SyncNode {
+    pub fn new(node: Box<dyn Node>) -> Self {
+        Self { node }
+    }
+}
+
+impl AsyncNode for SyncNode {
+    fn tick<'a>(&'a mut self, memory: &'a mut BlackBoard) -> TickFuture<'a> {
+        Box::pin(async move { self.node.tick(memory) })
+    }
+
+    fn reset(&mut self) {
+        self.node.reset();
+    }
+
+    fn get_name(&self) -> String {
+        self.node.get_name()
+    }
+}
+
+pub struct AsyncBehaviouralTree {
+    pub root: Box<dyn AsyncNode>,
+    pub blackboard: BlackBoard,
+    pub last_result: Option<NodeResult>
+}
+
+impl AsyncBehaviouralTree {
+    pub fn new(root: Box<dyn AsyncNode>, blackboard: BlackBoard) -> Self {
+        Self { root, blackboard, last_result: None }
+    }
+
+    pub async fn tick(&mut self, blackboard: &mut BlackBoard) -> NodeResult {
+        let result = self.root.tick(blackboard).await;
+        self.last_result = Some(result.clone());
+        result
+    }
+
+    pub fn reset(&mut self) {
+        self.root.reset();
+        self.last_result = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::{Pin, pin};
+    use std::task::{Context, Poll, Waker};
+
+    use crate::async_tree::{AsyncBehaviouralTree, AsyncNode, SyncNode, TickFuture};
+    use crate::blackboard::BlackBoard;
+    use crate::node::NodeResult;
+    use crate::nodes::action::Action;
+
+    /// Polls `future` to completion on the current thread, counting how often
+    /// it was pending.
+    fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        let mut pending = 0;
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return (output, pending),
+                Poll::Pending => pending += 1
+            }
+        }
+    }
+
+    struct YieldOnce {
+        yielded: bool
+    }
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+            if self.yielded {
+                return Poll::Ready(());
+            }
+            self.yielded = true;
+            context.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    struct ReadSensor;
+
+    impl AsyncNode for ReadSensor {
+        fn tick<'a>(&'a mut self, memory: &'a mut BlackBoard) -> TickFuture<'a> {
+            Box::pin(async move {
+                YieldOnce { yielded: false }.await;
+                memory.set("distance", 1.5_f64);
+                NodeResult::Passed
+            })
+        }
+
+        fn reset(&mut self) { }
+
+        fn get_name(&self) -> String {
+            "read_sensor".into()
+        }
+    }
+
+    #[test]
+    fn test_awaits_yielding_action() {
+        let mut tree = AsyncBehaviouralTree::new(Box::new(ReadSensor), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+
+        let (result, pending) = block_on(tree.tick(&mut memory));
+        assert_eq!(result, NodeResult::Passed);
+        assert_eq!(pending, 1);
+        assert_eq!(memory.get::<f64>("distance"), Some(&1.5));
+        assert_eq!(tree.last_result, Some(NodeResult::Passed));
+    }
+
+    #[test]
+    fn test_sync_node_adapter() {
+        let action = Action::new("wait".into(), |_| NodeResult::Running);
+        let mut tree = AsyncBehaviouralTree::new(Box::new(SyncNode::new(Box::new(action))), BlackBoard::new());
+        assert_eq!(tree.root.get_name(), "wait");
+
+        let (result, pending) = block_on(tree.tick(&mut BlackBoard::new()));
+        assert_eq!(result, NodeResult::Running);
+        assert_eq!(pending, 0);
+    }
+}
@@ -1,28 +1,739 @@
-use std::{any::Any, collections::HashMap};
+use std::{any::{Any, TypeId}, collections::{HashMap, HashSet}, marker::PhantomData, sync::Arc, time::{Duration, Instant}};
+
+use crate::clock::{Clock, SystemClock, TickContext};
+
+/// A blackboard key tied to the type of value stored under it, so reading a
+/// key back with the wrong type does not compile:
+///
+/// ```compile_fail
+/// use neurotree::blackboard::{BlackBoard, Key};
+///
+/// const BATTERY: Key<f64> = Key::new("battery");
+/// let memory = BlackBoard::new();
+/// let level: Option<&u32> = memory.get_keyed(BATTERY);
+/// ```
+pub struct Key<T> {
+    pub name: &'static str,
+    marker: PhantomData<fn() -> T>
+}
+
+impl<T> Key<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, marker: PhantomData }
+    }
+}
+
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> { }
+
+/// Declares a set of typed `Key` constants. Each key is stored under its
+/// constant's name, or under the string given after `=` to share a key with
+/// code that uses plain strings:
+///
+/// ```
+/// use neurotree::blackboard::BlackBoard;
+///
+/// mod keys {
+///     neurotree::blackboard_keys! {
+///         pub BATTERY: f64,
+///         pub POS: (f64, f64) = "position",
+///     }
+/// }
+///
+/// let mut memory = BlackBoard::new();
+/// memory.set_keyed(keys::POS, (1.0, 2.0));
+/// assert_eq!(memory.get::<(f64, f64)>("position"), Some(&(1.0, 2.0)));
+/// assert_eq!(keys::BATTERY.name, "BATTERY");
+/// ```
+#[macro_export]
+macro_rules! blackboard_keys {
+    ($($vis:vis $name:ident : $ty:ty $(= $key:literal)?),* $(,)?) => {
+        $(
+            $vis const $name: $crate::blackboard::Key<$ty> =
+                $crate::blackboard::Key::new($crate::blackboard_keys!(@key $name $($key)?));
+        )*
+    };
+    (@key $name:ident) => { stringify!($name) };
+    (@key $name:ident $key:literal) => { $key };
+}
+
+pub type ChangeObserver = Box<dyn FnMut(&str) + Send + Sync>;
+
+type BoxedValue = Box<dyn Any + Send + Sync>;
+
+fn clone_value<T: Clone + Send + Sync + 'static>(value: &BoxedValue) -> Option<BoxedValue> {
+    Some(Box::new(value.downcast_ref::<T>()?.clone()))
+}
+
+/// The blackboard as it was at `BlackBoard::snapshot`, for rolling back
+/// speculative writes with `BlackBoard::restore`.
+pub struct BlackBoardSnapshot {
+    entries: HashMap<String, BoxedValue>,
+    expiries: HashMap<String, Instant>,
+    keys: HashSet<String>
+}
 
 pub struct BlackBoard {
-    pub data: HashMap<String, Box<dyn Any + Send + Sync>>
+    pub data: HashMap<String, BoxedValue>,
+    observers: Vec<ChangeObserver>,
+    cloners: HashMap<TypeId, fn(&BoxedValue) -> Option<BoxedValue>>,
+    key_versions: HashMap<String, u64>,
+    expiries: HashMap<String, Instant>,
+    pub(crate) context: Option<TickContext>,
+    clock: Arc<dyn Clock>,
+    version: u64
+}
+
+impl Default for BlackBoard {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BlackBoard {
     pub fn new() -> Self {
         Self {
-            data: HashMap::new()
+            data: HashMap::new(),
+            observers: Vec::new(),
+            cloners: HashMap::new(),
+            key_versions: HashMap::new(),
+            expiries: HashMap::new(),
+            context: None,
+            clock: Arc::new(SystemClock),
+            version: 0
+        }
+    }
+
+    /// The clock TTL entries expire by, unless a tick context is set.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn now(&self) -> Instant {
+        TickContext::now_or(self, self.clock.as_ref())
+    }
+
+    /// Goes up with every `set` or `update` and with every entry deleted by
+    /// `remove` or `clear`, so callers can tell whether anything changed
+    /// since they last looked. Writes through `get_mut` or `data`
+    /// are not counted.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The `version` at which `key` last changed, counted the same way, or
+    /// `0` if it never did. Lets a reader watch one key rather than all.
+    pub fn key_version(&self, key: &str) -> u64 {
+        self.key_versions.get(key).copied().unwrap_or(0)
+    }
+
+    /// Registers `observer` to be called with the key of every `set` or
+    /// `update` and of every entry deleted by `remove` or `clear`. Writes
+    /// made directly through `data` are not observed.
+    pub fn on_change(&mut self, observer: ChangeObserver) {
+        self.observers.push(observer);
+    }
+
+    pub(crate) fn notify(&mut self, key: &str) {
+        self.version += 1;
+        self.key_versions.insert(key.to_string(), self.version);
+        for observer in &mut self.observers {
+            observer(key);
         }
     }
 
-    pub fn get<T: 'static + Send + Sync>(&mut self, key: &str, value: T) {
-        self.data.insert(key.to_string(), Box::new(value));
+    fn is_expired(&self, key: &str) -> bool {
+        self.expiries.get(key).is_some_and(|expiry| self.now() >= *expiry)
+    }
+
+    /// The value under `key`, if there is one, it is a `T` and it has not
+    /// expired.
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        if self.is_expired(key) {
+            return None;
+        }
+        self.data.get(key)?.downcast_ref::<T>()
+    }
+
+    /// Like `get`, also deleting the entry if it has expired.
+    pub fn get_mut<T: 'static>(&mut self, key: &str) -> Option<&mut T> {
+        if self.is_expired(key) {
+            self.remove(key);
+            return None;
+        }
+        self.data.get_mut(key)?.downcast_mut::<T>()
+    }
+
+    /// A copy of the `T` under `key`, for values read once and kept.
+    pub fn get_cloned<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        self.get::<T>(key).cloned()
+    }
+
+    /// Applies `f` to the `T` under `key` in place, returning whether there
+    /// was one to change. Unlike writes through `get_mut` this notifies
+    /// observers, like `set`, without cloning the value out and back in.
+    pub fn update<T: 'static>(&mut self, key: &str, f: impl FnOnce(&mut T)) -> bool {
+        let Some(value) = self.get_mut::<T>(key) else {
+            return false;
+        };
+        f(value);
+        self.notify(key);
+        true
+    }
+
+    pub fn set<T: 'static + Send + Sync>(&mut self, key: &str, value: T) {
+        self.insert_entry(key, Box::new(value), None);
+    }
+
+    /// `set` for a value that is already boxed, e.g. moved from another
+    /// blackboard, keeping its expiry if it has one.
+    pub(crate) fn insert_entry(&mut self, key: &str, value: BoxedValue, expiry: Option<Instant>) {
+        match expiry {
+            Some(expiry) => self.expiries.insert(key.to_string(), expiry),
+            None => self.expiries.remove(key)
+        };
+        self.data.insert(key.to_string(), value);
+        self.notify(key);
+    }
+
+    /// Takes the entry under `key` out, with its expiry, to move it to
+    /// another blackboard and back. Observers are not told, since the value
+    /// is expected to return; an expired entry is deleted instead.
+    pub(crate) fn take_entry(&mut self, key: &str) -> Option<(BoxedValue, Option<Instant>)> {
+        if self.is_expired(key) {
+            self.remove(key);
+            return None;
+        }
+        let value = self.data.remove(key)?;
+        Some((value, self.expiries.remove(key)))
+    }
+
+    /// Like `set`, for facts that go stale, e.g. where an enemy was last
+    /// seen. Time is taken from the tick context if there is one, else from
+    /// the blackboard's clock. Once `ttl` has passed the entry reads as
+    /// missing, and it is deleted by `get_mut`, `remove_expired` or the
+    /// next `BehaviouralTree::tick`. A `ttl` too large to represent never
+    /// expires, and a later `set` makes the key permanent again.
+    pub fn set_with_ttl<T: 'static + Send + Sync>(&mut self, key: &str, value: T, ttl: Duration) {
+        let expiry = self.now().checked_add(ttl);
+        self.insert_entry(key, Box::new(value), expiry);
+    }
+
+    /// Deletes every expired entry, notifying observers of each.
+    pub fn remove_expired(&mut self) {
+        if self.expiries.is_empty() {
+            return;
+        }
+        let now = self.now();
+        let expired: Vec<String> = self.expiries.iter()
+            .filter(|(_, expiry)| now >= **expiry)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            self.remove(&key);
+        }
+    }
+
+    /// The `T` under `key`, inserting `default()` first if the key is
+    /// missing, like `HashMap::entry(key).or_insert_with(default)`.
+    ///
+    /// # Panics
+    ///
+    /// If the key already holds a value of another type.
+    pub fn entry_or_insert_with<T: 'static + Send + Sync>(&mut self, key: &str, default: impl FnOnce() -> T) -> &mut T {
+        if !self.contains_key(key) {
+            self.set(key, default());
+        }
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("blackboard key `{key}` does not hold a `{}`", std::any::type_name::<T>())
+        }
+    }
+
+    pub fn get_keyed<T: 'static>(&self, key: Key<T>) -> Option<&T> {
+        self.get(key.name)
+    }
+
+    pub fn get_keyed_mut<T: 'static>(&mut self, key: Key<T>) -> Option<&mut T> {
+        self.get_mut(key.name)
+    }
+
+    pub fn set_keyed<T: 'static + Send + Sync>(&mut self, key: Key<T>, value: T) {
+        self.set(key.name, value);
     }
 
     pub fn contains_key(&self, key: &str) -> bool {
         if !self.data.contains_key(key) {
             return false;
         }
-        true
+        !self.is_expired(key)
+    }
+
+    /// Every key currently stored, in no particular order. Values are
+    /// `dyn Any` and can't be shown generically, but the keys are enough to
+    /// audit what a tree reads and writes.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.data.keys().map(String::as_str).filter(|key| !self.is_expired(key))
     }
 
+    pub fn len(&self) -> usize {
+        self.keys().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Deletes the entry under `key`, returning whether there was one.
     pub fn remove(&mut self, key: &str) -> bool {
-        self.data.get(key).is_some()
+        self.expiries.remove(key);
+        let removed = self.data.remove(key).is_some();
+        if removed {
+            self.notify(key);
+        }
+        removed
+    }
+
+    /// Deletes every entry, notifying observers of each key removed.
+    pub fn clear(&mut self) {
+        self.expiries.clear();
+        let keys: Vec<String> = self.data.drain().map(|(key, _)| key).collect();
+        for key in keys {
+            self.notify(&key);
+        }
+    }
+
+    /// Lets snapshots capture values of type `T`. Values are stored as
+    /// `Box<dyn Any>`, so only registered types can be cloned.
+    pub fn register_clone<T: Clone + Send + Sync + 'static>(&mut self) {
+        self.cloners.insert(TypeId::of::<T>(), clone_value::<T>);
+    }
+
+    /// Clones every entry whose type was registered with `register_clone`.
+    pub fn snapshot(&self) -> BlackBoardSnapshot {
+        let entries: HashMap<String, BoxedValue> = self.data.iter()
+            .filter_map(|(key, value)| {
+                let clone = self.cloners.get(&value.as_ref().type_id())?;
+                Some((key.clone(), clone(value)?))
+            })
+            .collect();
+        let expiries = self.expiries.iter()
+            .filter(|(key, _)| entries.contains_key(*key))
+            .map(|(key, expiry)| (key.clone(), *expiry))
+            .collect();
+        BlackBoardSnapshot { entries, expiries, keys: self.data.keys().cloned().collect() }
+    }
+
+    /// Rolls back to `snapshot`: keys added since are removed and captured
+    /// values are put back. Entries of unregistered types that existed at
+    /// the snapshot keep their current value. Observers see every key put
+    /// back or removed.
+    pub fn restore(&mut self, snapshot: BlackBoardSnapshot) {
+        let added: Vec<String> = self.data.keys().filter(|key| !snapshot.keys.contains(*key)).cloned().collect();
+        for key in added {
+            self.remove(&key);
+        }
+        for (key, value) in snapshot.entries {
+            let expiry = snapshot.expiries.get(&key).copied();
+            self.insert_entry(&key, value, expiry);
+        }
+    }
+
+    /// A view that stores every key as `<prefix>/<key>`, so reused subtrees
+    /// can each keep e.g. their own `target` without clobbering one another.
+    pub fn scoped(&mut self, prefix: &str) -> ScopedBlackBoard<'_> {
+        ScopedBlackBoard { parent: self, prefix: prefix.to_string() }
+    }
+}
+
+/// A prefixed view of a `BlackBoard`, see `BlackBoard::scoped`. Reads and
+/// writes go straight to the parent, observers included.
+pub struct ScopedBlackBoard<'a> {
+    parent: &'a mut BlackBoard,
+    prefix: String
+}
+
+impl ScopedBlackBoard<'_> {
+    /// The key as it is stored in the parent blackboard.
+    pub fn full_key(&self, key: &str) -> String {
+        format!("{}/{key}", self.prefix)
+    }
+
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.parent.get(&self.full_key(key))
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, key: &str) -> Option<&mut T> {
+        let key = self.full_key(key);
+        self.parent.get_mut(&key)
+    }
+
+    pub fn set<T: 'static + Send + Sync>(&mut self, key: &str, value: T) {
+        let key = self.full_key(key);
+        self.parent.set(&key, value);
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.parent.contains_key(&self.full_key(key))
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        let key = self.full_key(key);
+        self.parent.remove(&key)
+    }
+
+    /// A scope nested inside this one, prefixing keys with both prefixes.
+    pub fn scoped(&mut self, prefix: &str) -> ScopedBlackBoard<'_> {
+        let prefix = self.full_key(prefix);
+        ScopedBlackBoard { parent: self.parent, prefix }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use crate::blackboard::{BlackBoard, Key};
+    use crate::clock::{ManualClock, TickContext};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Plan {
+        steps: Vec<String>
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut memory = BlackBoard::new();
+        assert_eq!(memory.get::<i32>("health"), None);
+
+        memory.set("health", 80_i32);
+        assert_eq!(memory.get::<i32>("health"), Some(&80));
+        assert_eq!(memory.get::<f64>("health"), None);
+
+        *memory.get_mut::<i32>("health").unwrap() -= 30;
+        assert_eq!(memory.get::<i32>("health"), Some(&50));
+
+        memory.set("health", "full");
+        assert_eq!(memory.get::<i32>("health"), None);
+        assert_eq!(memory.get::<&str>("health"), Some(&"full"));
+    }
+
+    #[test]
+    fn test_remove_and_clear() {
+        let mut memory = BlackBoard::new();
+        memory.set("enemy_seen", true);
+        memory.set("target", 3_u32);
+
+        assert!(memory.remove("enemy_seen"));
+        assert!(!memory.contains_key("enemy_seen"));
+        assert!(!memory.remove("enemy_seen"));
+
+        memory.clear();
+        assert!(memory.data.is_empty());
+    }
+
+    mod keys {
+        crate::blackboard_keys! {
+            pub BATTERY: f64,
+            pub TARGET: Option<u32> = "target",
+        }
+    }
+
+    #[test]
+    fn test_declared_keys() {
+        let mut memory = BlackBoard::new();
+        memory.set_keyed(keys::BATTERY, 0.5);
+        memory.set_keyed(keys::TARGET, Some(3));
+
+        assert_eq!(keys::BATTERY.name, "BATTERY");
+        assert_eq!(memory.get_keyed(keys::BATTERY), Some(&0.5));
+        assert_eq!(memory.get::<Option<u32>>("target"), Some(&Some(3)));
+
+        *memory.get_keyed_mut(keys::TARGET).unwrap() = None;
+        assert_eq!(memory.get_keyed(keys::TARGET), Some(&None));
+    }
+
+    #[test]
+    fn test_keyed_access() {
+        const BATTERY: Key<f64> = Key::new("battery");
+        const TARGET: Key<u32> = Key::new("target");
+        let mut memory = BlackBoard::new();
+        assert_eq!(memory.get_keyed(BATTERY), None);
+
+        memory.set_keyed(BATTERY, 0.75);
+        memory.set_keyed(TARGET, 3);
+        assert_eq!(memory.get_keyed(BATTERY), Some(&0.75));
+        assert_eq!(memory.get_keyed(TARGET), Some(&3));
+
+        *memory.get_keyed_mut(TARGET).unwrap() += 1;
+        assert_eq!(memory.get::<u32>("target"), Some(&4));
+
+        // A string write of another type under the same name is not a `T`.
+        memory.set("battery", 1_u32);
+        assert_eq!(memory.get_keyed(BATTERY), None);
+    }
+
+    #[test]
+    fn test_observer_sees_changed_keys() {
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let mut memory = BlackBoard::new();
+        let seen = changed.clone();
+        memory.on_change(Box::new(move |key| seen.lock().unwrap().push(key.to_string())));
+
+        memory.set("target", 3_u32);
+        memory.set("enemy_seen", true);
+        memory.remove("target");
+        memory.remove("missing");
+        assert_eq!(*changed.lock().unwrap(), vec!["target", "enemy_seen", "target"]);
+
+        memory.set("health", 80_i32);
+        changed.lock().unwrap().clear();
+        memory.clear();
+        let mut cleared = changed.lock().unwrap().clone();
+        cleared.sort();
+        assert_eq!(cleared, vec!["enemy_seen", "health"]);
+    }
+
+    #[test]
+    fn test_scopes_do_not_interfere() {
+        let mut memory = BlackBoard::new();
+        memory.set("target", 0_u32);
+        memory.scoped("left_arm").set("target", 1_u32);
+        memory.scoped("right_arm").set("target", 2_u32);
+
+        assert_eq!(memory.scoped("left_arm").get::<u32>("target"), Some(&1));
+        assert_eq!(memory.scoped("right_arm").get::<u32>("target"), Some(&2));
+        assert_eq!(memory.get::<u32>("target"), Some(&0));
+        assert_eq!(memory.get::<u32>("left_arm/target"), Some(&1));
+
+        *memory.scoped("left_arm").get_mut::<u32>("target").unwrap() += 10;
+        assert!(memory.scoped("right_arm").remove("target"));
+        assert_eq!(memory.scoped("left_arm").get::<u32>("target"), Some(&11));
+        assert!(!memory.scoped("right_arm").contains_key("target"));
+
+        memory.scoped("left_arm").scoped("hand").set("grip", true);
+        assert_eq!(memory.get::<bool>("left_arm/hand/grip"), Some(&true));
+    }
+
+    #[test]
+    fn test_entry_or_insert_with() {
+        let mut memory = BlackBoard::new();
+        *memory.entry_or_insert_with("hits", || 0_u32) += 1;
+        *memory.entry_or_insert_with("hits", || 100_u32) += 1;
+        assert_eq!(memory.get::<u32>("hits"), Some(&2));
+
+        memory.entry_or_insert_with("path", Vec::<u8>::new).push(4);
+        assert_eq!(memory.entry_or_insert_with("path", Vec::<u8>::new), &vec![4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not hold")]
+    fn test_entry_type_mismatch_panics() {
+        let mut memory = BlackBoard::new();
+        memory.set("hits", 1.5_f64);
+        memory.entry_or_insert_with("hits", || 0_u32);
+    }
+
+    #[test]
+    fn test_version_counts_changes() {
+        let mut memory = BlackBoard::new();
+        assert_eq!(memory.version(), 0);
+
+        memory.set("target", 3_u32);
+        memory.remove("missing");
+        assert_eq!(memory.version(), 1);
+        memory.remove("target");
+        assert_eq!(memory.version(), 2);
+
+        memory.set("health", 80_i32);
+        memory.set("enemy_seen", true);
+        memory.clear();
+        assert_eq!(memory.version(), 6);
+        memory.clear();
+        assert_eq!(memory.version(), 6);
+    }
+
+    #[test]
+    fn test_restore_rolls_back_registered_values() {
+        let mut memory = BlackBoard::new();
+        memory.register_clone::<i32>();
+        memory.register_clone::<Plan>();
+        memory.set("health", 80_i32);
+        memory.set("plan", Plan { steps: vec!["walk".into()] });
+
+        let snapshot = memory.snapshot();
+        memory.set("health", 10_i32);
+        memory.get_mut::<Plan>("plan").unwrap().steps.push("jump".into());
+        memory.set("target", 3_i32);
+        memory.remove("plan");
+
+        memory.restore(snapshot);
+        assert_eq!(memory.get::<i32>("health"), Some(&80));
+        assert_eq!(memory.get::<Plan>("plan"), Some(&Plan { steps: vec!["walk".into()] }));
+        assert!(!memory.contains_key("target"));
+    }
+
+    #[test]
+    fn test_restore_keeps_unregistered_values() {
+        let mut memory = BlackBoard::new();
+        memory.register_clone::<i32>();
+        memory.set("health", 80_i32);
+        memory.set("name", String::from("scout"));
+
+        let snapshot = memory.snapshot();
+        memory.set("health", 10_i32);
+        memory.set("name", String::from("guard"));
+        memory.restore(snapshot);
+
+        assert_eq!(memory.get::<i32>("health"), Some(&80));
+        assert_eq!(memory.get::<String>("name").map(String::as_str), Some("guard"));
+    }
+
+    #[test]
+    fn test_get_cloned() {
+        let mut memory = BlackBoard::new();
+        memory.set("plan", Plan { steps: vec!["walk".into()] });
+
+        let mut plan = memory.get_cloned::<Plan>("plan").unwrap();
+        plan.steps.push("jump".into());
+        assert_eq!(memory.get::<Plan>("plan").unwrap().steps.len(), 1);
+        assert_eq!(memory.get_cloned::<i32>("plan"), None);
+        assert_eq!(memory.get_cloned::<Plan>("missing"), None);
+    }
+
+    #[test]
+    fn test_update_in_place() {
+        let mut memory = BlackBoard::new();
+        memory.set("plan", Plan { steps: vec!["walk".into()] });
+        let version = memory.version();
+
+        assert!(memory.update::<Plan>("plan", |plan| plan.steps.push("jump".into())));
+        assert_eq!(memory.get::<Plan>("plan").unwrap().steps, vec!["walk", "jump"]);
+        assert_eq!(memory.version(), version + 1);
+
+        assert!(!memory.update::<Plan>("missing", |plan| plan.steps.clear()));
+        assert!(!memory.update::<i32>("plan", |value| *value += 1));
+        assert_eq!(memory.version(), version + 1);
+    }
+
+    #[test]
+    fn test_key_version_tracks_single_key() {
+        let mut memory = BlackBoard::new();
+        assert_eq!(memory.key_version("health"), 0);
+
+        memory.set("health", 80_i32);
+        let health = memory.key_version("health");
+        memory.set("target", 3_u32);
+        assert_eq!(memory.key_version("health"), health);
+
+        memory.update::<i32>("health", |health| *health -= 10);
+        assert!(memory.key_version("health") > health);
+
+        let before = memory.key_version("target");
+        memory.clear();
+        assert!(memory.key_version("target") > before);
+    }
+
+    #[test]
+    fn test_keys_and_len() {
+        let mut memory = BlackBoard::new();
+        assert!(memory.is_empty());
+        assert_eq!(memory.keys().count(), 0);
+
+        memory.set("health", 80_i32);
+        memory.set("target", "door");
+        memory.scoped("arm").set("target", 3_u32);
+
+        let mut keys: Vec<&str> = memory.keys().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["arm/target", "health", "target"]);
+        assert_eq!(memory.len(), 3);
+        assert!(!memory.is_empty());
+    }
+
+    fn ttl_memory(clock: &ManualClock) -> BlackBoard {
+        BlackBoard::new().with_clock(Arc::new(clock.clone()))
+    }
+
+    #[test]
+    fn test_ttl_entry_expires() {
+        let clock = ManualClock::new();
+        let mut memory = ttl_memory(&clock);
+        memory.set_with_ttl("enemy_seen", (3.0, 4.0), Duration::from_millis(20));
+        memory.set("health", 80_i32);
+        assert_eq!(memory.get::<(f64, f64)>("enemy_seen"), Some(&(3.0, 4.0)));
+        assert!(memory.contains_key("enemy_seen"));
+
+        clock.advance(Duration::from_millis(20));
+        assert_eq!(memory.get::<(f64, f64)>("enemy_seen"), None);
+        assert!(!memory.contains_key("enemy_seen"));
+        assert_eq!(memory.len(), 1);
+
+        memory.remove_expired();
+        assert!(!memory.data.contains_key("enemy_seen"));
+        assert_eq!(memory.get::<i32>("health"), Some(&80));
+    }
+
+    #[test]
+    fn test_set_makes_ttl_key_permanent() {
+        let clock = ManualClock::new();
+        let mut memory = ttl_memory(&clock);
+        memory.set_with_ttl("enemy_seen", 1_u32, Duration::from_millis(10));
+        memory.set("enemy_seen", 2_u32);
+
+        clock.advance(Duration::from_millis(20));
+        assert_eq!(memory.get::<u32>("enemy_seen"), Some(&2));
+
+        memory.set_with_ttl("enemy_seen", 3_u32, Duration::ZERO);
+        assert_eq!(memory.get_mut::<u32>("enemy_seen"), None);
+        assert!(!memory.data.contains_key("enemy_seen"));
+    }
+
+    #[test]
+    fn test_huge_ttl_never_expires() {
+        let clock = ManualClock::new();
+        let mut memory = ttl_memory(&clock);
+        memory.set_with_ttl("home", 1_u32, Duration::MAX);
+
+        clock.advance(Duration::from_secs(1_000_000));
+        assert_eq!(memory.get::<u32>("home"), Some(&1));
+    }
+
+    #[test]
+    fn test_ttl_follows_tick_context() {
+        let mut memory = BlackBoard::new();
+        let start = Instant::now() + Duration::from_secs(3600);
+        let context = TickContext::new(start, Duration::from_secs(1));
+        context.run(&mut memory, |memory| memory.set_with_ttl("enemy_seen", true, Duration::from_secs(2)));
+
+        let later = TickContext::new(start + Duration::from_secs(2), Duration::from_secs(1));
+        assert!(context.run(&mut memory, |memory| memory.contains_key("enemy_seen")));
+        assert!(!later.run(&mut memory, |memory| memory.contains_key("enemy_seen")));
+    }
+
+    #[test]
+    fn test_restore_keeps_ttl() {
+        let clock = ManualClock::new();
+        let mut memory = ttl_memory(&clock);
+        memory.register_clone::<u32>();
+        memory.set_with_ttl("enemy_seen", 1_u32, Duration::from_secs(1));
+        memory.set("target", 2_u32);
+
+        let snapshot = memory.snapshot();
+        memory.set("enemy_seen", 5_u32);
+        memory.set_with_ttl("target", 6_u32, Duration::from_millis(10));
+        memory.restore(snapshot);
+
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(memory.get::<u32>("target"), Some(&2));
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(memory.get::<u32>("enemy_seen"), None);
     }
 }
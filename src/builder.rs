@@ -0,0 +1,151 @@
+//! Fluent construction of trees without boxing every node by hand:
+//!
+//! ```
+//! use neurotree::builder::TreeBuilder;
+//! use neurotree::node::NodeResult;
+//!
+//! let root = TreeBuilder::sequence("root")
+//!     .child(TreeBuilder::action("look", |_| NodeResult::Passed))
+//!     .child(TreeBuilder::selector("move")
+//!         .child(TreeBuilder::action("run", |_| NodeResult::Failed))
+//!         .child(TreeBuilder::action("walk", |_| NodeResult::Running)))
+//!     .build();
+//! ```
+
+use std::time::Duration;
+
+use crate::blackboard::BlackBoard;
+use crate::config::NodeConfig;
+use crate::node::{Node, NodeResult};
+use crate::nodes::action::Action;
+use crate::nodes::inverter::Inverter;
+use crate::nodes::parallel::{Parallel, ParallelPolicy};
+use crate::nodes::selector::Selector;
+use crate::nodes::sequence::Sequence;
+use crate::nodes::timeout::Timeout;
+use crate::registry::NodeRegistry;
+use crate::tree::BehaviouralTree;
+
+/// Anything that can be placed in a tree: a node, a boxed node or a builder.
+pub trait IntoNode {
+    fn into_node(self) -> Box<dyn Node>;
+}
+
+impl<T: Node> IntoNode for T {
+    fn into_node(self) -> Box<dyn Node> {
+        Box::new(self)
+    }
+}
+
+impl IntoNode for Box<dyn Node> {
+    fn into_node(self) -> Box<dyn Node> {
+        self
+    }
+}
+
+impl IntoNode for CompositeBuilder {
+    fn into_node(self) -> Box<dyn Node> {
+        self.build()
+    }
+}
+
+enum CompositeKind {
+    Sequence,
+    Selector,
+    Parallel(ParallelPolicy)
+}
+
+/// Collects the children of a composite; see `TreeBuilder`.
+pub struct CompositeBuilder {
+    kind: CompositeKind,
+    name: String,
+    children: Vec<Box<dyn Node>>
+}
+
+impl CompositeBuilder {
+    pub fn child(mut self, child: impl IntoNode) -> Self {
+        self.children.push(child.into_node());
+        self
+    }
+
+    pub fn build(self) -> Box<dyn Node> {
+        match self.kind {
+            CompositeKind::Sequence => Box::new(Sequence::new(self.name, self.children)),
+            CompositeKind::Selector => Box::new(Selector::new(self.name, self.children)),
+            CompositeKind::Parallel(policy) => Box::new(Parallel::new(self.name, policy, self.children))
+        }
+    }
+
+    /// Builds the composite and wraps it in a tree with an empty blackboard.
+    pub fn into_tree(self) -> BehaviouralTree {
+        BehaviouralTree::new(self.build(), BlackBoard::new())
+    }
+}
+
+pub struct TreeBuilder;
+
+impl TreeBuilder {
+    pub fn sequence(name: &str) -> CompositeBuilder {
+        CompositeBuilder { kind: CompositeKind::Sequence, name: name.into(), children: Vec::new() }
+    }
+
+    pub fn selector(name: &str) -> CompositeBuilder {
+        CompositeBuilder { kind: CompositeKind::Selector, name: name.into(), children: Vec::new() }
+    }
+
+    pub fn parallel(name: &str, policy: ParallelPolicy) -> CompositeBuilder {
+        CompositeBuilder { kind: CompositeKind::Parallel(policy), name: name.into(), children: Vec::new() }
+    }
+
+    pub fn action(name: &str, action: impl FnMut(&mut BlackBoard) -> NodeResult + Send + Sync + 'static) -> Action {
+        Action::new(name.into(), action)
+    }
+
+    pub fn inverter(name: &str, child: impl IntoNode) -> Inverter {
+        Inverter::new(child.into_node(), name.into())
+    }
+
+    pub fn timeout(name: &str, limit: Duration, child: impl IntoNode) -> Timeout {
+        Timeout::new(name.into(), limit, child.into_node())
+    }
+
+    /// A node of a kind registered at runtime, see `NodeRegistry`.
+    pub fn registered(registry: &NodeRegistry, kind: &str, params: &NodeConfig, children: Vec<Box<dyn Node>>) -> Result<Box<dyn Node>, String> {
+        registry.create(kind, params, children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::builder::TreeBuilder;
+    use crate::node::{NodeResult, running_path};
+    use crate::nodes::parallel::ParallelPolicy;
+
+    #[test]
+    fn test_two_level_tree() {
+        let mut tree = TreeBuilder::sequence("root")
+            .child(TreeBuilder::inverter("calm", TreeBuilder::action("enemy", |_| NodeResult::Failed)))
+            .child(TreeBuilder::selector("move")
+                .child(TreeBuilder::action("run", |_| NodeResult::Failed))
+                .child(TreeBuilder::timeout("walk_limit", Duration::from_secs(60), TreeBuilder::action("walk", |_| NodeResult::Running))))
+            .into_tree();
+
+        let names: Vec<_> = tree.root.children().iter().map(|child| child.get_name()).collect();
+        assert_eq!(names, ["calm", "move"]);
+        assert_eq!(tree.tick(&mut BlackBoard::new()), NodeResult::Running);
+        assert_eq!(running_path(tree.root.as_ref()), ["root", "move", "walk_limit", "walk"]);
+    }
+
+    #[test]
+    fn test_parallel_builder() {
+        let mut root = TreeBuilder::parallel("both", ParallelPolicy::RequireAll)
+            .child(TreeBuilder::action("a", |_| NodeResult::Passed))
+            .child(TreeBuilder::action("b", |_| NodeResult::Passed))
+            .build();
+        assert_eq!(root.kind(), "Parallel");
+        assert_eq!(root.tick(&mut BlackBoard::new()), NodeResult::Passed);
+    }
+}
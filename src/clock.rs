@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+
+/// Source of the current time for time-based nodes. Nodes default to
+/// `SystemClock`; tests and simulations inject a `ManualClock` instead.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so a
+/// test can keep one handle and hand another to the tree.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO))
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed.lock().unwrap()
+    }
+}
+
+/// The time of one tick, shared by every node in it. Ticked through
+/// `Node::tick_ctx`, time-based nodes read `now` from here instead of their
+/// own clock, through `now_or`, so a whole tree sees the same time and a
+/// simulation can step it by any `dt` it likes. `dt` is for custom nodes
+/// that integrate once per tick; the crate's nodes measure their intervals
+/// between `now`s, since they are not necessarily ticked every step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickContext {
+    pub now: Instant,
+    pub dt: Duration
+}
+
+impl TickContext {
+    pub fn new(now: Instant, dt: Duration) -> Self {
+        Self { now, dt }
+    }
+
+    /// The context of the tick in progress, if it was started with one.
+    pub fn current(memory: &BlackBoard) -> Option<TickContext> {
+        memory.context
+    }
+
+    /// The current tick's `now`, falling back to `clock` outside a context
+    /// tick.
+    pub fn now_or(memory: &BlackBoard, clock: &dyn Clock) -> Instant {
+        Self::current(memory).map_or_else(|| clock.now(), |context| context.now)
+    }
+
+    /// Runs `tick` with this context set on the blackboard, putting back
+    /// whatever context was set before.
+    pub(crate) fn run<R>(&self, memory: &mut BlackBoard, tick: impl FnOnce(&mut BlackBoard) -> R) -> R {
+        let previous = memory.context.replace(*self);
+        let result = tick(memory);
+        memory.context = previous;
+        result
+    }
+}
@@ -0,0 +1,176 @@
+//! Per-node configuration: a node's kind, name and parameters without its
+//! children, so single nodes can be edited, stored and rebuilt on their own.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::node::NodeResult;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConfigValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Duration(Duration),
+    Result(NodeResult)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeConfig {
+    pub kind: String,
+    pub name: String,
+    pub params: BTreeMap<String, ConfigValue>
+}
+
+impl NodeConfig {
+    pub fn new(kind: String, name: String) -> Self {
+        Self { kind, name, params: BTreeMap::new() }
+    }
+
+    pub fn with(mut self, key: &str, value: ConfigValue) -> Self {
+        self.params.insert(key.into(), value);
+        self
+    }
+
+    /// Fails unless the config describes a node of `kind`.
+    pub fn expect_kind(&self, kind: &str) -> Result<(), String> {
+        if self.kind != kind {
+            return Err(format!("expected a {kind} config, got {}", self.kind));
+        }
+        Ok(())
+    }
+
+    fn param(&self, key: &str) -> Result<&ConfigValue, String> {
+        self.params.get(key).ok_or_else(|| format!("{} '{}' is missing parameter '{key}'", self.kind, self.name))
+    }
+
+    fn mismatch(&self, key: &str, expected: &str) -> String {
+        format!("{} '{}' parameter '{key}' is not a {expected}", self.kind, self.name)
+    }
+
+    pub fn bool(&self, key: &str) -> Result<bool, String> {
+        match self.param(key)? {
+            ConfigValue::Bool(value) => Ok(*value),
+            _ => Err(self.mismatch(key, "bool"))
+        }
+    }
+
+    pub fn int(&self, key: &str) -> Result<i64, String> {
+        match self.param(key)? {
+            ConfigValue::Int(value) => Ok(*value),
+            _ => Err(self.mismatch(key, "integer"))
+        }
+    }
+
+    /// An integer parameter used as a count or limit, so it must not be
+    /// negative.
+    pub fn count(&self, key: &str) -> Result<usize, String> {
+        let value = self.int(key)?;
+        usize::try_from(value).map_err(|_| format!("{} '{}' parameter '{key}' must not be negative, got {value}", self.kind, self.name))
+    }
+
+    pub fn float(&self, key: &str) -> Result<f64, String> {
+        match self.param(key)? {
+            ConfigValue::Float(value) => Ok(*value),
+            ConfigValue::Int(value) => Ok(*value as f64),
+            _ => Err(self.mismatch(key, "number"))
+        }
+    }
+
+    pub fn text(&self, key: &str) -> Result<String, String> {
+        match self.param(key)? {
+            ConfigValue::Text(value) => Ok(value.clone()),
+            _ => Err(self.mismatch(key, "string"))
+        }
+    }
+
+    pub fn duration(&self, key: &str) -> Result<Duration, String> {
+        match self.param(key)? {
+            ConfigValue::Duration(value) => Ok(*value),
+            _ => Err(self.mismatch(key, "duration"))
+        }
+    }
+
+    pub fn result(&self, key: &str) -> Result<NodeResult, String> {
+        match self.param(key)? {
+            ConfigValue::Result(value) => Ok(value.clone()),
+            _ => Err(self.mismatch(key, "node result"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::config::{ConfigValue, NodeConfig};
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::max_ticks::MaxTicks;
+    use crate::nodes::range_guard::RangeGuard;
+    use crate::nodes::sequence::Sequence;
+    use crate::nodes::timed_parallel::TimedParallel;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_action_round_trip() {
+        let action = Action::new("attack".into(), |_| NodeResult::Passed);
+        let config = action.to_config();
+        assert_eq!(config, NodeConfig::new("Action".into(), "attack".into()));
+
+        let mut rebuilt = Action::from_config(&config, |_| NodeResult::Passed).unwrap();
+        assert_eq!(rebuilt.get_name(), "attack");
+        assert_eq!(rebuilt.tick(&mut BlackBoard::new()), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_parallel_round_trip() {
+        let parallel = TimedParallel::new("team".into(), vec![
+            Box::new(MockNode::new("a", vec![NodeResult::Passed])),
+            Box::new(MockNode::new("b", vec![NodeResult::Passed])),
+        ], 2, Duration::from_millis(1500));
+        let config = parallel.to_config();
+        assert_eq!(config.kind, "TimedParallel");
+        assert_eq!(config.params.get("required"), Some(&ConfigValue::Int(2)));
+        assert_eq!(config.params.get("budget"), Some(&ConfigValue::Duration(Duration::from_millis(1500))));
+
+        let rebuilt = TimedParallel::from_config(&config, vec![]).unwrap();
+        assert_eq!((rebuilt.name.as_str(), rebuilt.required, rebuilt.budget), ("team", 2, Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_range_guard_round_trip() {
+        let guard = RangeGuard::new("speed_ok".into(), "speed".into(), -1.0, 2.5).unwrap().with_clamp();
+        let rebuilt = RangeGuard::from_config(&guard.to_config()).unwrap();
+        assert_eq!(rebuilt.to_config(), guard.to_config());
+        assert!(rebuilt.clamp);
+    }
+
+    #[test]
+    fn test_mismatched_config_is_rejected() {
+        let config = Sequence::new("root".into(), vec![]).to_config();
+        assert_eq!(config.kind, "Sequence");
+        assert!(config.params.is_empty());
+        assert!(RangeGuard::from_config(&config).is_err());
+
+        let config = NodeConfig::new("RangeGuard".into(), "guard".into()).with("key", ConfigValue::Int(3));
+        assert_eq!(RangeGuard::from_config(&config).err().unwrap(), "RangeGuard 'guard' parameter 'key' is not a string");
+    }
+
+    #[test]
+    fn test_negative_counts_are_rejected() {
+        let config = NodeConfig::new("MaxTicks".into(), "budget".into()).with("limit", ConfigValue::Int(-3));
+        assert_eq!(config.count("limit").err().unwrap(), "MaxTicks 'budget' parameter 'limit' must not be negative, got -3");
+        assert!(MaxTicks::from_config(&config, Box::new(MockNode::new("work", vec![NodeResult::Passed]))).is_err());
+
+        let config = NodeConfig::new("TimedParallel".into(), "team".into())
+            .with("required", ConfigValue::Int(-1))
+            .with("budget", ConfigValue::Duration(Duration::from_secs(1)));
+        assert!(TimedParallel::from_config(&config, vec![]).is_err());
+        assert_eq!(config.with("required", ConfigValue::Int(2)).count("required"), Ok(2));
+    }
+}
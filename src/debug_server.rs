@@ -0,0 +1,259 @@
+//! A tiny TCP endpoint for inspecting a running tree from another process.
+//! Enabled with the `debug-server` feature.
+//!
+//! A client connects, sends a line (`snapshot`) and receives a single JSON
+//! document with the blackboard and the tree snapshot, after which the
+//! connection is closed. Nothing happens on a background thread: the host
+//! calls `DebugServer::poll` from its own loop, between ticks, and the call
+//! never blocks: an answer a slow client can't take at once is sent over
+//! the following polls.
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+
+use serde_json::{Map, Value, json};
+
+use crate::blackboard::BlackBoard;
+use crate::node::NodeResult;
+use crate::tree::BehaviouralTree;
+
+/// Longest request line accepted; clients sending more are dropped.
+const MAX_REQUEST: usize = 1024;
+
+struct Client {
+    stream: TcpStream,
+    request: Vec<u8>,
+    response: Option<(Vec<u8>, usize)>
+}
+
+enum Progress {
+    Pending,
+    Answered,
+    Closed
+}
+
+pub struct DebugServer {
+    listener: TcpListener,
+    clients: Vec<Client>
+}
+
+impl DebugServer {
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, clients: Vec::new() })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts new clients, reads their requests and writes whatever part
+    /// of their answers the sockets take without blocking. Returns the
+    /// number of clients whose answer was sent in full. A client that errors,
+    /// sends an unknown request or a line over `MAX_REQUEST` bytes is
+    /// dropped without affecting the others.
+    pub fn poll(&mut self, tree: &BehaviouralTree, memory: &BlackBoard) -> io::Result<usize> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nonblocking(true).is_ok() {
+                        self.clients.push(Client { stream, request: Vec::new(), response: None });
+                    }
+                },
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if matches!(error.kind(), ErrorKind::ConnectionAborted | ErrorKind::ConnectionReset | ErrorKind::Interrupted) => continue,
+                Err(error) => return Err(error)
+            }
+        }
+
+        let mut answered = 0;
+        let mut index = 0;
+        while index < self.clients.len() {
+            match advance(&mut self.clients[index], tree, memory) {
+                Progress::Pending => index += 1,
+                Progress::Answered => {
+                    answered += 1;
+                    self.clients.swap_remove(index);
+                },
+                Progress::Closed => {
+                    self.clients.swap_remove(index);
+                }
+            }
+        }
+        Ok(answered)
+    }
+}
+
+fn advance(client: &mut Client, tree: &BehaviouralTree, memory: &BlackBoard) -> Progress {
+    if client.response.is_none() {
+        let mut buffer = [0u8; 256];
+        match client.stream.read(&mut buffer) {
+            Ok(0) => return Progress::Closed,
+            Ok(read) => client.request.extend_from_slice(&buffer[..read]),
+            Err(error) if error.kind() == ErrorKind::WouldBlock => return Progress::Pending,
+            Err(_) => return Progress::Closed
+        }
+        let Some(end) = client.request.iter().position(|byte| *byte == b'\n') else {
+            return match client.request.len() > MAX_REQUEST {
+                true => Progress::Closed,
+                false => Progress::Pending
+            };
+        };
+        if end > MAX_REQUEST || String::from_utf8_lossy(&client.request[..end]).trim() != "snapshot" {
+            return Progress::Closed;
+        }
+        let body = json!({
+            "blackboard": blackboard_json(memory),
+            "tree": tree.snapshot()
+        });
+        client.response = Some((format!("{body}\n").into_bytes(), 0));
+    }
+
+    let Some((response, written)) = &mut client.response else {
+        return Progress::Pending;
+    };
+    while *written < response.len() {
+        match client.stream.write(&response[*written..]) {
+            Ok(0) => return Progress::Closed,
+            Ok(count) => *written += count,
+            Err(error) if error.kind() == ErrorKind::WouldBlock => return Progress::Pending,
+            Err(_) => return Progress::Closed
+        }
+    }
+    Progress::Answered
+}
+
+/// Blackboard values of common types as JSON; anything else is reported
+/// as `"<opaque>"`, since the blackboard holds arbitrary `Any` values.
+pub fn blackboard_json(memory: &BlackBoard) -> Value {
+    let mut entries = Map::new();
+    for (key, value) in &memory.data {
+        let value = if let Some(value) = value.downcast_ref::<bool>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<i32>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<i64>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<u32>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<u64>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<usize>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<f32>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<f64>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<String>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<&str>() {
+            json!(value)
+        } else if let Some(value) = value.downcast_ref::<NodeResult>() {
+            json!(value)
+        } else {
+            json!("<opaque>")
+        };
+        entries.insert(key.clone(), value);
+    }
+    Value::Object(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use crate::blackboard::BlackBoard;
+    use crate::debug_server::DebugServer;
+    use crate::node::NodeResult;
+    use crate::nodes::sequence::Sequence;
+    use crate::tests::MockNode;
+    use crate::tree::BehaviouralTree;
+
+    #[test]
+    fn test_client_receives_snapshot() {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(MockNode::new("walk", vec![NodeResult::Running])),
+        ]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+        memory.data.insert("health".into(), Box::new(75_i32));
+        memory.data.insert("target".into(), Box::new(String::from("door")));
+        memory.data.insert("path".into(), Box::new(vec![1, 2, 3]));
+        tree.tick(&mut memory);
+
+        let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(b"snapshot\n").unwrap();
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).unwrap();
+            line
+        });
+
+        let mut answered = 0;
+        for _ in 0..500 {
+            answered += server.poll(&tree, &memory).unwrap();
+            if answered > 0 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(2));
+        }
+        assert_eq!(answered, 1);
+
+        let response: Value = serde_json::from_str(&client.join().unwrap()).unwrap();
+        assert_eq!(response["blackboard"]["health"], 75);
+        assert_eq!(response["blackboard"]["target"], "door");
+        assert_eq!(response["blackboard"]["path"], "<opaque>");
+        assert_eq!(response["tree"]["nodes"][0]["name"], "root");
+        assert_eq!(response["tree"]["nodes"][0]["status"], "Running");
+        assert_eq!(response["tree"]["nodes"][1]["status"], "Running");
+    }
+
+    #[test]
+    fn test_oversized_request_drops_only_that_client() {
+        let root = MockNode::new("idle", vec![NodeResult::Passed]);
+        let tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let memory = BlackBoard::new();
+        let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+        let address = server.local_addr().unwrap();
+
+        let mut flood = TcpStream::connect(address).unwrap();
+        flood.write_all(&[b'x'; 4096]).unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(b"snapshot\n").unwrap();
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).unwrap();
+            line
+        });
+
+        let mut answered = 0;
+        for _ in 0..500 {
+            answered += server.poll(&tree, &memory).unwrap();
+            if answered > 0 && server.clients.is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(2));
+        }
+        assert_eq!(answered, 1);
+        assert!(client.join().unwrap().contains("\"tree\""));
+
+        let mut rest = Vec::new();
+        assert_eq!(flood.read_to_end(&mut rest).map(|_| rest.len()).unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_poll_without_clients_returns_immediately() {
+        let root = MockNode::new("idle", vec![NodeResult::Passed]);
+        let tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+        assert_eq!(server.poll(&tree, &BlackBoard::new()).unwrap(), 0);
+    }
+}
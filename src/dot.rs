@@ -0,0 +1,87 @@
+//! Graphviz export. Render the output with e.g. `dot -Tsvg tree.dot`.
+
+use crate::node::Node;
+use crate::tree::BehaviouralTree;
+
+impl BehaviouralTree {
+    /// The tree as a DOT digraph. Nodes are named `n<id>` with ids taken in
+    /// pre-order, labelled with their name and kind, and linked to their
+    /// children in tick order. The shape follows the node's kind: conditions
+    /// are diamonds, decorators hexagons, composites ellipses, and actions as
+    /// well as kinds from outside the crate boxes.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph BehaviouralTree {\n");
+        write_node(self.root.as_ref(), &mut 0, &mut out);
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn shape(kind: &str) -> &'static str {
+    match kind.split('<').next().unwrap_or(kind) {
+        "Condition" | "CachedCondition" | "SharedCondition" | "RangeGuard" => "diamond",
+        "Inverter" | "Succeeder" | "Failer" | "Repeat" | "RepeatN" | "RepeatWhile" | "UntilFail" | "UntilSuccess"
+            | "Retry" | "RetryOn" | "Timeout" | "Delay" | "Cooldown" | "Guard" | "Once" | "LimitExecutions"
+            | "MaxTicks" | "RateLimiter" | "FrameBudget" | "Memoize" | "ConditionCache" | "Breadcrumb"
+            | "ErrorCapture" | "WriteStatus" | "StreamStatus" | "Exclusive" | "OrderCheck" | "SubTree"
+            | "HotReload" | "Profiled" | "Traced" | "Metered" => "hexagon",
+        "Sequence" | "Selector" | "Parallel" | "TimedParallel" | "VoteParallel" | "ReactiveSequence"
+            | "ReactiveSelector" | "AbortableSequence" | "AbortableSelector" | "BudgetedSequence"
+            | "FallthroughSelector" | "HysteresisSelector" | "LeastRecentlyRun"
+            | "PrioritySelector" | "RandomSelector" | "WeightedRandomSelector" | "UtilitySelector" | "Pipeline"
+            | "Switch" | "HashRouter" | "InboxExecutor" => "ellipse",
+        _ => "box"
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn write_node(node: &dyn Node, next_id: &mut usize, out: &mut String) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+    let kind = node.kind();
+    out.push_str(&format!(
+        "    n{id} [label=\"{}\\n{}\", shape={}];\n",
+        escape(&node.get_name()), escape(&kind), shape(&kind)
+    ));
+    for child in node.children() {
+        let child_id = write_node(child, next_id, out);
+        out.push_str(&format!("    n{id} -> n{child_id};\n"));
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::NodeResult;
+    use crate::nodes::action::Action;
+    use crate::nodes::condition::Condition;
+    use crate::nodes::inverter::Inverter;
+    use crate::nodes::selector::Selector;
+    use crate::nodes::sequence::Sequence;
+    use crate::tree::BehaviouralTree;
+
+    #[test]
+    fn test_dot_edges_and_shapes() {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(Condition::new("door \"front\"".into(), |_| true)),
+            Box::new(Inverter::new(Box::new(Action::new("locked".into(), |_| NodeResult::Failed)), "unlocked".into())),
+            Box::new(Selector::new("fallback".into(), vec![Box::new(Action::new("knock".into(), |_| NodeResult::Passed))])),
+        ]);
+        let dot = BehaviouralTree::new(Box::new(root), BlackBoard::new()).to_dot();
+
+        assert!(dot.starts_with("digraph BehaviouralTree {\n"));
+        assert!(dot.contains("n0 [label=\"root\\nSequence\", shape=ellipse];"));
+        assert!(dot.contains("n1 [label=\"door \\\"front\\\"\\nCondition\", shape=diamond];"));
+        assert!(dot.contains("n2 [label=\"unlocked\\nInverter\", shape=hexagon];"));
+        assert!(dot.contains("n3 [label=\"locked\\nAction\", shape=box];"));
+        assert!(dot.contains("n4 [label=\"fallback\\nSelector\", shape=ellipse];"));
+        for edge in ["n0 -> n1;", "n0 -> n2;", "n2 -> n3;", "n0 -> n4;", "n4 -> n5;"] {
+            assert!(dot.contains(edge), "missing {edge}");
+        }
+        assert_eq!(dot.matches("->").count(), 5);
+    }
+}
@@ -2,11 +2,37 @@ pub mod node;
 pub mod blackboard;
 pub mod tree;
 pub mod nodes;
+pub mod plan;
+pub mod clock;
+pub mod rng;
+pub mod snapshot;
+pub mod dot;
+pub mod config;
+pub mod builder;
+pub mod registry;
+pub mod stats;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+#[cfg(feature = "debug-server")]
+pub mod debug_server;
+#[cfg(feature = "async")]
+pub mod async_tree;
+#[cfg(feature = "persistence")]
+pub mod persistence;
+#[cfg(feature = "loader")]
+pub mod loader;
 
 #[cfg(test)]
-
 mod tests {
-    use crate::{blackboard::BlackBoard, node::{Node, NodeResult}};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::{blackboard::BlackBoard, clock::{Clock, ManualClock, TickContext}, node::{Node, NodeId, NodeResult}};
 
     #[test]
     fn test_node_result_variants() {
@@ -16,7 +42,8 @@ mod tests {
     }
 
     pub struct TestNode {
-        result: NodeResult
+        result: NodeResult,
+        id: NodeId
     }
 
     impl Node for TestNode {
@@ -27,6 +54,10 @@ mod tests {
         fn get_name(&self) -> String {
             "IS_ENEMY".into()
         }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
         fn reset(&mut self) {
             
         }
@@ -35,11 +66,76 @@ mod tests {
     #[test]
     fn test_mock_node() {
         let mut blackboard = BlackBoard::new();
-        let mut node = TestNode { result: NodeResult::Passed };
+        let mut node = TestNode { result: NodeResult::Passed, id: NodeId::next() };
         assert_eq!(node.tick(&mut blackboard), NodeResult::Passed);
         node.result = NodeResult::Failed;
         assert_eq!(node.tick(&mut blackboard), NodeResult::Failed);
         node.result = NodeResult::Running;
         assert_eq!(node.tick(&mut blackboard), NodeResult::Running);
     }
-}
\ No newline at end of file
+
+    /// A one-second step `secs` seconds after `clock`'s current time, for
+    /// checking that nodes follow the context rather than their own clock.
+    pub fn context_at(clock: &ManualClock, secs: u64) -> TickContext {
+        TickContext::new(clock.now() + Duration::from_secs(secs), Duration::from_secs(1))
+    }
+
+    /// Shared view of how often a `MockNode` was ticked and reset.
+    #[derive(Clone, Default)]
+    pub struct Probe {
+        ticks: Arc<AtomicUsize>,
+        resets: Arc<AtomicUsize>
+    }
+
+    impl Probe {
+        pub fn ticks(&self) -> usize {
+            self.ticks.load(Ordering::SeqCst)
+        }
+
+        pub fn resets(&self) -> usize {
+            self.resets.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Replays `script` one result per tick, repeating the last entry once
+    /// the script runs out. `reset` rewinds to the start of the script.
+    pub struct MockNode {
+        name: String,
+        script: Vec<NodeResult>,
+        position: usize,
+        probe: Probe,
+        id: NodeId
+    }
+
+    impl MockNode {
+        pub fn new(name: &str, script: Vec<NodeResult>) -> Self {
+            Self { name: name.into(), script, position: 0, probe: Probe::default(), id: NodeId::next() }
+        }
+
+        pub fn probe(&self) -> Probe {
+            self.probe.clone()
+        }
+    }
+
+    impl Node for MockNode {
+        fn tick(&mut self, _memory: &mut BlackBoard) -> NodeResult {
+            self.probe.ticks.fetch_add(1, Ordering::SeqCst);
+            let index = self.position.min(self.script.len() - 1);
+            self.position += 1;
+            self.script[index].clone()
+        }
+
+        fn get_name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
+
+        fn reset(&mut self) {
+            self.probe.resets.fetch_add(1, Ordering::SeqCst);
+            self.position = 0;
+        }
+    }
+}
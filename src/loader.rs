@@ -0,0 +1,224 @@
+//! Builds trees from a declarative JSON description, so designers can edit
+//! trees without recompiling. Enabled with the `loader` feature.
+//!
+//! Every node is an object with a `type`, a `name`, its children under
+//! `children` (composites) or `child` (decorators), and its parameters as
+//! further fields:
+//!
+//! ```json
+//! { "type": "sequence", "name": "root", "children": [
+//!     { "type": "condition", "name": "door_closed" },
+//!     { "type": "parallel", "name": "open", "policy": "require_count", "count": 1, "children": [
+//!         { "type": "action", "name": "push" },
+//!         { "type": "inverter", "name": "not_locked", "child": { "type": "action", "name": "locked" } }
+//!     ] }
+//! ] }
+//! ```
+//!
+//! Actions and conditions are looked up by name in a registry of
+//! factories supplied by the caller, since their code can't be stored.
+//! Other types can be added with a `NodeRegistry`, which receives the
+//! node's fields as a `NodeConfig` together with its built `children`, or
+//! its one `child`.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::Node;
+use crate::nodes::inverter::Inverter;
+use crate::nodes::parallel::Parallel;
+use crate::nodes::repeat::Repeat;
+use crate::nodes::selector::Selector;
+use crate::nodes::sequence::Sequence;
+use crate::registry::NodeRegistry;
+
+pub type LeafFactory = Box<dyn Fn() -> Box<dyn Node>>;
+
+/// Parses `text` and builds the tree it describes.
+pub fn load_json(text: &str, leaves: &HashMap<String, LeafFactory>) -> Result<Box<dyn Node>, String> {
+    load_json_with(text, leaves, &NodeRegistry::new())
+}
+
+/// Like `load_json`, building types it doesn't know through `registry`.
+pub fn load_json_with(text: &str, leaves: &HashMap<String, LeafFactory>, registry: &NodeRegistry) -> Result<Box<dyn Node>, String> {
+    let value: Value = serde_json::from_str(text).map_err(|error| format!("invalid tree description: {error}"))?;
+    load_value(&value, leaves, registry)
+}
+
+pub fn load_value(value: &Value, leaves: &HashMap<String, LeafFactory>, registry: &NodeRegistry) -> Result<Box<dyn Node>, String> {
+    let object = value.as_object().ok_or_else(|| format!("expected a node object, got {value}"))?;
+    let kind = object.get("type").and_then(Value::as_str).ok_or("node is missing its \"type\"")?;
+    let name = object.get("name").and_then(Value::as_str).ok_or_else(|| format!("{kind} node is missing its \"name\""))?;
+    let config = config(kind, name, object)?;
+
+    let node: Box<dyn Node> = match kind {
+        "action" | "condition" => {
+            let factory = leaves.get(name).ok_or_else(|| format!("no {kind} registered under '{name}'"))?;
+            factory()
+        },
+        "sequence" => Box::new(Sequence::new(name.into(), children(&config, object, leaves, registry)?)),
+        "selector" => Box::new(Selector::new(name.into(), children(&config, object, leaves, registry)?)),
+        "parallel" => {
+            let children = children(&config, object, leaves, registry)?;
+            Box::new(Parallel::from_config(&NodeConfig { kind: "Parallel".into(), ..config }, children)?)
+        },
+        "inverter" => Box::new(Inverter::new(child(&config, object, leaves, registry)?, name.into())),
+        "repeat" => {
+            let child = child(&config, object, leaves, registry)?;
+            Box::new(Repeat::from_config(&NodeConfig { kind: "Repeat".into(), ..config }, child)?)
+        },
+        other if registry.contains(other) => {
+            let children = match (object.contains_key("children"), object.contains_key("child")) {
+                (true, _) => children(&config, object, leaves, registry)?,
+                (false, true) => vec![child(&config, object, leaves, registry)?],
+                (false, false) => Vec::new()
+            };
+            registry.create(other, &config, children)?
+        },
+        other => return Err(format!("unknown node type '{other}' for '{name}'"))
+    };
+    Ok(node)
+}
+
+/// The node's scalar fields as a `NodeConfig`, leaving out `type`, `name`
+/// and the children.
+fn config(kind: &str, name: &str, object: &Map<String, Value>) -> Result<NodeConfig, String> {
+    let mut config = NodeConfig::new(kind.into(), name.into());
+    for (key, value) in object {
+        if matches!(key.as_str(), "type" | "name" | "child" | "children") {
+            continue;
+        }
+        let value = match value {
+            Value::Bool(value) => ConfigValue::Bool(*value),
+            Value::Number(number) => match number.as_i64() {
+                Some(value) => ConfigValue::Int(value),
+                None => ConfigValue::Float(number.as_f64().unwrap_or(f64::NAN))
+            },
+            Value::String(value) => ConfigValue::Text(value.clone()),
+            _ => return Err(format!("{kind} '{name}' parameter '{key}' must be a bool, number or string"))
+        };
+        config.params.insert(key.clone(), value);
+    }
+    Ok(config)
+}
+
+fn children(config: &NodeConfig, object: &Map<String, Value>, leaves: &HashMap<String, LeafFactory>, registry: &NodeRegistry) -> Result<Vec<Box<dyn Node>>, String> {
+    let children = object.get("children").and_then(Value::as_array)
+        .ok_or_else(|| format!("{} '{}' needs a \"children\" array", config.kind, config.name))?;
+    children.iter().map(|child| load_value(child, leaves, registry)).collect()
+}
+
+fn child(config: &NodeConfig, object: &Map<String, Value>, leaves: &HashMap<String, LeafFactory>, registry: &NodeRegistry) -> Result<Box<dyn Node>, String> {
+    let child = object.get("child").ok_or_else(|| format!("{} '{}' needs a \"child\"", config.kind, config.name))?;
+    load_value(child, leaves, registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::loader::{LeafFactory, load_json, load_json_with};
+    use crate::node::NodeResult;
+    use crate::nodes::action::Action;
+    use crate::nodes::condition::Condition;
+    use crate::nodes::range_guard::RangeGuard;
+    use crate::nodes::repeat::{Repeat, RepeatMode};
+    use crate::nodes::timeout::Timeout;
+    use crate::registry::{NodeRegistry, no_children};
+
+    fn leaves() -> HashMap<String, LeafFactory> {
+        let mut leaves: HashMap<String, LeafFactory> = HashMap::new();
+        leaves.insert("door_closed".into(), Box::new(|| Box::new(Condition::new("door_closed".into(), |bb| {
+            !bb.get::<bool>("door_open").copied().unwrap_or(false)
+        }))));
+        leaves.insert("push".into(), Box::new(|| Box::new(Action::new("push".into(), |bb| {
+            bb.set("door_open", true);
+            NodeResult::Passed
+        }))));
+        leaves.insert("locked".into(), Box::new(|| Box::new(Action::new("locked".into(), |_| NodeResult::Failed))));
+        leaves
+    }
+
+    const TREE: &str = r#"{ "type": "sequence", "name": "root", "children": [
+        { "type": "condition", "name": "door_closed" },
+        { "type": "parallel", "name": "open", "policy": "require_all", "children": [
+            { "type": "action", "name": "push" },
+            { "type": "inverter", "name": "not_locked", "child": { "type": "action", "name": "locked" } }
+        ] }
+    ] }"#;
+
+    #[test]
+    fn test_load_and_tick() {
+        let mut root = load_json(TREE, &leaves()).unwrap();
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(root.get_name(), "root");
+        assert_eq!(root.children()[1].kind(), "Parallel");
+        assert_eq!(root.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<bool>("door_open"), Some(&true));
+        assert_eq!(root.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_descriptive_errors() {
+        let error = |text: &str| load_json(text, &leaves()).err().unwrap();
+        assert_eq!(error(r#"{ "type": "blackboard_dance", "name": "x" }"#), "unknown node type 'blackboard_dance' for 'x'");
+        assert_eq!(error(r#"{ "type": "action", "name": "fly" }"#), "no action registered under 'fly'");
+        assert_eq!(error(r#"{ "type": "repeat", "name": "again" }"#), "repeat 'again' needs a \"child\"");
+        assert!(error(r#"{ "type": "parallel", "name": "p", "policy": "most", "children": [] }"#).contains("unknown policy 'most'"));
+        assert_eq!(
+            error(r#"{ "type": "parallel", "name": "p", "policy": "require_count", "count": -1, "children": [] }"#),
+            "Parallel 'p' parameter 'count' must not be negative, got -1"
+        );
+    }
+
+    #[test]
+    fn test_repeat_mode() {
+        let repeat = |mode: &str| {
+            let text = format!(r#"{{ "type": "repeat", "name": "again", {mode} "child": {{ "type": "action", "name": "locked" }} }}"#);
+            let node = load_json(&text, &leaves()).unwrap();
+            let any: Box<dyn Any> = node;
+            any.downcast::<Repeat>().unwrap().mode
+        };
+        assert_eq!(repeat(""), RepeatMode::Forever);
+        assert_eq!(repeat(r#""mode": "until_fail","#), RepeatMode::UntilFail);
+        assert_eq!(repeat(r#""mode": "while_success","#), RepeatMode::WhileSuccess);
+    }
+
+    #[test]
+    fn test_registered_types() {
+        let mut registry = NodeRegistry::new();
+        registry.register("RangeGuard", |config, children| {
+            no_children(config, &children)?;
+            Ok(Box::new(RangeGuard::from_config(config)?))
+        });
+        registry.register("Timeout", |config, mut children| {
+            let child = children.pop().filter(|_| children.is_empty()).ok_or("Timeout takes exactly one child")?;
+            Ok(Box::new(Timeout::new(config.name.clone(), Duration::from_secs_f64(config.float("seconds")?), child)))
+        });
+        let text = r#"{ "type": "sequence", "name": "root", "children": [
+            { "type": "RangeGuard", "name": "speed_ok", "key": "speed", "min": 0, "max": 2.5, "clamp": false },
+            { "type": "Timeout", "name": "push_limit", "seconds": 2, "child": { "type": "action", "name": "push" } }
+        ] }"#;
+        let mut root = load_json_with(text, &leaves(), &registry).unwrap();
+        let mut memory = BlackBoard::new();
+
+        memory.set("speed", 4.0_f64);
+        assert_eq!(root.tick(&mut memory), NodeResult::Failed);
+        memory.set("speed", 1.0_f64);
+        assert_eq!(root.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(root.children()[1].children()[0].get_name(), "push");
+
+        let text = r#"{ "type": "RangeGuard", "name": "speed_ok", "key": "speed", "min": 0, "max": 1,
+            "child": { "type": "action", "name": "push" } }"#;
+        assert_eq!(load_json_with(text, &leaves(), &registry).err().unwrap(), "RangeGuard 'speed_ok' does not take children");
+
+        let text = r#"{ "type": "RangeGuard", "name": "speed_ok", "key": "speed", "min": 3, "max": 1 }"#;
+        assert_eq!(load_json_with(text, &leaves(), &registry).err().unwrap(), "RangeGuard 'speed_ok' needs min <= max, got min 3 and max 1");
+    }
+}
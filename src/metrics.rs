@@ -0,0 +1,162 @@
+//! Prometheus-style metrics for node ticks. Enabled with the `metrics` feature.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NodeCounters {
+    pub ticks: u64,
+    pub passed: u64,
+    pub failed: u64,
+    pub running: u64
+}
+
+/// Shared tick counters keyed by node name. Clones share the same counters,
+/// so one handle can be scraped while others are held by `Metered` nodes.
+#[derive(Debug, Default, Clone)]
+pub struct Metrics {
+    counters: Arc<Mutex<BTreeMap<String, NodeCounters>>>
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, node: &str, result: &NodeResult) {
+        let mut counters = self.counters.lock().unwrap();
+        let entry = counters.entry(node.to_string()).or_default();
+        entry.ticks += 1;
+        match result {
+            NodeResult::Passed => entry.passed += 1,
+            NodeResult::Failed => entry.failed += 1,
+            NodeResult::Running => entry.running += 1
+        }
+    }
+
+    pub fn counters(&self, node: &str) -> Option<NodeCounters> {
+        self.counters.lock().unwrap().get(node).cloned()
+    }
+
+    /// Renders all counters in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let counters = self.counters.lock().unwrap();
+        let mut text = String::new();
+
+        text.push_str("# HELP neurotree_node_ticks_total Number of times a node was ticked.\n");
+        text.push_str("# TYPE neurotree_node_ticks_total counter\n");
+        for (node, counter) in counters.iter() {
+            let _ = writeln!(text, "neurotree_node_ticks_total{{node=\"{}\"}} {}", escape(node), counter.ticks);
+        }
+
+        text.push_str("# HELP neurotree_node_results_total Number of tick results per node and result.\n");
+        text.push_str("# TYPE neurotree_node_results_total counter\n");
+        for (node, counter) in counters.iter() {
+            for (result, count) in [("passed", counter.passed), ("failed", counter.failed), ("running", counter.running)] {
+                let _ = writeln!(text, "neurotree_node_results_total{{node=\"{}\",result=\"{}\"}} {}", escape(node), result, count);
+            }
+        }
+        text
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Records every tick of its child, under the child's name, in `metrics`.
+pub struct Metered {
+    pub child: Box<dyn Node>,
+    pub metrics: Metrics,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Metered {
+    pub fn new(name: String, metrics: Metrics, child: Box<dyn Node>) -> Self {
+        Self { child, metrics, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        self.metrics.record(&self.child.get_name(), &result);
+        result
+    }
+}
+
+impl Node for Metered {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::metrics::{Metered, Metrics, NodeCounters};
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::sequence::Sequence;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_exported_counters() {
+        let metrics = Metrics::new();
+        let scan = MockNode::new("scan", vec![NodeResult::Passed]);
+        let walk = MockNode::new("walk \"fast\"", vec![NodeResult::Running, NodeResult::Running, NodeResult::Failed]);
+        let mut root = Sequence::new("root".into(), vec![
+            Box::new(Metered::new("scan_metrics".into(), metrics.clone(), Box::new(scan))),
+            Box::new(Metered::new("walk_metrics".into(), metrics.clone(), Box::new(walk))),
+        ]);
+        let mut memory = BlackBoard::new();
+        for _ in 0..3 {
+            root.tick(&mut memory);
+        }
+
+        assert_eq!(metrics.counters("scan"), Some(NodeCounters { ticks: 1, passed: 1, failed: 0, running: 0 }));
+        let text = metrics.render();
+        assert!(text.contains("# TYPE neurotree_node_ticks_total counter\n"));
+        assert!(text.contains("neurotree_node_ticks_total{node=\"scan\"} 1\n"));
+        assert!(text.contains("neurotree_node_results_total{node=\"walk \\\"fast\\\"\",result=\"running\"} 2\n"));
+        assert!(text.contains("neurotree_node_results_total{node=\"walk \\\"fast\\\"\",result=\"failed\"} 1\n"));
+    }
+}
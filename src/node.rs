@@ -1,14 +1,213 @@
+use std::any::Any;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use crate::blackboard::BlackBoard;
+use crate::clock::TickContext;
+use crate::config::NodeConfig;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NodeResult {
     Passed,
     Failed,
     Running
 }
 
-pub trait Node: Send + Sync {
+/// A result together with the reason for a failure, see `Node::tick_outcome`.
+/// Compares equal to a bare `NodeResult` with the same variant, so checks
+/// written against `NodeResult` keep working.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeOutcome {
+    pub result: NodeResult,
+    pub message: Option<String>
+}
+
+impl NodeOutcome {
+    pub fn new(result: NodeResult) -> Self {
+        Self { result, message: None }
+    }
+
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self { result: NodeResult::Failed, message: Some(message.into()) }
+    }
+}
+
+impl PartialEq<NodeResult> for NodeOutcome {
+    fn eq(&self, other: &NodeResult) -> bool {
+        self.result == *other
+    }
+}
+
+impl From<NodeResult> for NodeOutcome {
+    fn from(result: NodeResult) -> Self {
+        Self::new(result)
+    }
+}
+
+static NEXT_NODE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identifies one node instance, unlike names which may repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeId(pub u64);
+
+impl NodeId {
+    /// A fresh id, never handed out before in this process.
+    pub fn next() -> Self {
+        Self(NEXT_NODE_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Told when each traced node starts and finishes a tick, see
+/// `Node::tick_traced`. Closures taking a name and a result implement it
+/// and are called when a node finishes.
+pub trait TickObserver {
+    fn enter(&mut self, _id: NodeId, _name: &str) { }
+    fn exit(&mut self, id: NodeId, name: &str, result: &NodeResult);
+}
+
+impl<F: FnMut(&str, NodeResult)> TickObserver for F {
+    fn exit(&mut self, _id: NodeId, name: &str, result: &NodeResult) {
+        self(name, result.clone());
+    }
+}
+
+pub type Trace<'a> = &'a mut dyn TickObserver;
+
+pub trait Node: Any + Send + Sync {
     fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult;
     fn reset(&mut self);
     fn get_name(&self) -> String;
+
+    /// The id this node was given when it was created, usually from
+    /// `NodeId::next`. It stays the same for the node's whole life.
+    fn id(&self) -> NodeId;
+
+    /// The child that returned `Running` on the last tick, if any.
+    /// Only meaningful while this node itself is running.
+    fn running_child(&self) -> Option<&dyn Node> {
+        None
+    }
+
+    /// Why the last tick returned `Failed`, for nodes that can tell.
+    /// Composites and decorators pass on the error of the child they failed
+    /// on, and report none when they failed on their own, e.g. a `Timeout`.
+    fn last_error(&self) -> Option<String> {
+        None
+    }
+
+    /// Ticks like `tick`, adding `last_error` as the message of a failure.
+    fn tick_outcome(&mut self, memory: &mut BlackBoard) -> NodeOutcome {
+        let result = self.tick(memory);
+        let message = match result {
+            NodeResult::Failed => self.last_error(),
+            _ => None
+        };
+        NodeOutcome { result, message }
+    }
+
+    /// Ticks like `tick` with `context` as the time of the tick for every
+    /// time-based node below. A plain `tick` leaves each of them to its own
+    /// clock, which is the system clock unless one was injected.
+    fn tick_ctx(&mut self, memory: &mut BlackBoard, context: &TickContext) -> NodeResult {
+        context.run(memory, |memory| self.tick(memory))
+    }
+
+    /// Ticks like `tick`, telling `trace` when this node starts and
+    /// finishes. Every composite and decorator in the crate overrides it and
+    /// ticks its children with `tick_child`, so the whole path of a tick is
+    /// reported, each child inside its parent. Custom nodes with children
+    /// must do the same; the default reports only the node itself.
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let (id, name) = (self.id(), self.get_name());
+        trace.enter(id, &name);
+        let result = self.tick(memory);
+        trace.exit(id, &name, &result);
+        result
+    }
+
+    /// Aborts a running node, e.g. when a higher-priority event fires. Unlike
+    /// `reset`, nodes may override it to run cleanup for the work they
+    /// abandon. Composites that know their running child pass the interrupt
+    /// to that child only and reset their own progress; the default resets.
+    fn interrupt(&mut self) {
+        self.reset();
+    }
+
+    /// Releases whatever a running node holds (locks, handles) when it is
+    /// reset or interrupted before it finished. Nodes that hold resources
+    /// track whether they are running and call this from `reset`, so it fires
+    /// once per abandoned run and never after a node completed.
+    fn on_halt(&mut self) { }
+
+    /// Direct children in tick order, for walking the tree. Leaves have none.
+    fn children(&self) -> Vec<&dyn Node> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        Vec::new()
+    }
+
+    /// Freezes the node's timers, e.g. while an operator takes over. The
+    /// default forwards to the children; nodes that measure time override it
+    /// so that the time spent suspended is not counted once `resume` is called.
+    /// Like a tick, they take the time from `memory`'s tick context if set.
+    fn suspend(&mut self, memory: &BlackBoard) {
+        for child in self.children_mut() {
+            child.suspend(memory);
+        }
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        for child in self.children_mut() {
+            child.resume(memory);
+        }
+    }
+
+    /// The node's type name without its module path, e.g. `Sequence` or
+    /// `HashRouter<u64>`.
+    fn kind(&self) -> String {
+        let full = std::any::type_name::<Self>();
+        let (path, generics) = full.split_at(full.find('<').unwrap_or(full.len()));
+        let name = path.rsplit("::").next().unwrap_or(path);
+        format!("{name}{generics}")
+    }
+
+    /// The node's kind, name and parameters. Children are not included;
+    /// composites are rebuilt with their children passed in separately.
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new(self.kind(), self.get_name())
+    }
+}
+
+/// Ticks `child`, traced when there is a `trace`, for composites that
+/// share one implementation between `tick` and `tick_traced`.
+pub fn tick_child(child: &mut dyn Node, memory: &mut BlackBoard, trace: &mut Option<Trace>) -> NodeResult {
+    match trace {
+        Some(trace) => child.tick_traced(memory, &mut **trace),
+        None => child.tick(memory)
+    }
+}
+
+/// `tick_child` for nodes that report why they failed: when `child` fails,
+/// its `last_error` is kept in `error`.
+pub fn tick_child_with_error(child: &mut dyn Node, memory: &mut BlackBoard, trace: &mut Option<Trace>, error: &mut Option<String>) -> NodeResult {
+    let result = tick_child(child, memory, trace);
+    if result == NodeResult::Failed {
+        *error = child.last_error();
+    }
+    result
+}
+
+/// Names from `node` down through each `running_child`, i.e. the path that
+/// is currently active below `node`.
+pub fn running_path(node: &dyn Node) -> Vec<String> {
+    let mut names = vec![node.get_name()];
+    let mut current = node.running_child();
+    while let Some(child) = current {
+        names.push(child.get_name());
+        current = child.running_child();
+    }
+    names
 }
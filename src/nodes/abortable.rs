@@ -0,0 +1,273 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+fn abort_requested(memory: &BlackBoard, key: &str) -> bool {
+    memory.get::<bool>(key).copied().unwrap_or(false)
+}
+
+/// Interrupts the running child, if there is one, and fails.
+fn abort(children: &mut [Box<dyn Node>], current: &mut Option<usize>) -> NodeResult {
+    if let Some(running) = current.take() {
+        children[running].interrupt();
+    }
+    NodeResult::Failed
+}
+
+/// A sequence that checks the `bool` under `abort_key` before ticking each
+/// child. When it is `true` the running child is interrupted and the
+/// sequence fails straight away. The flag is left for the caller to clear.
+/// Like `Sequence` it resumes at the running child, so the children that
+/// already passed are not run again.
+pub struct AbortableSequence {
+    pub children: Vec<Box<dyn Node>>,
+    pub abort_key: String,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl AbortableSequence {
+    pub fn new(name: String, abort_key: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self { children, abort_key, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        for index in self.current_child.unwrap_or(0)..self.children.len() {
+            if abort_requested(memory, &self.abort_key) {
+                return abort(&mut self.children, &mut self.current_child);
+            }
+            let result = tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error);
+            if result == NodeResult::Running {
+                self.current_child = Some(index);
+                return result;
+            }
+            self.current_child = None;
+            if result == NodeResult::Failed {
+                return result;
+            }
+        }
+        NodeResult::Passed
+    }
+}
+
+impl Node for AbortableSequence {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+/// A selector with the same abort check as `AbortableSequence`. Like
+/// `Selector` it resumes at the running child, so the children that already
+/// failed are not tried again.
+pub struct AbortableSelector {
+    pub children: Vec<Box<dyn Node>>,
+    pub abort_key: String,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl AbortableSelector {
+    pub fn new(name: String, abort_key: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self { children, abort_key, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        for index in self.current_child.unwrap_or(0)..self.children.len() {
+            if abort_requested(memory, &self.abort_key) {
+                return abort(&mut self.children, &mut self.current_child);
+            }
+            let result = tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error);
+            if result == NodeResult::Running {
+                self.current_child = Some(index);
+                return result;
+            }
+            self.current_child = None;
+            if result == NodeResult::Passed {
+                return result;
+            }
+        }
+        NodeResult::Failed
+    }
+}
+
+impl Node for AbortableSelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::abortable::{AbortableSelector, AbortableSequence};
+    use crate::nodes::action::Action;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_sequence_aborts_mid_run() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running]);
+        let (first_probe, second_probe) = (first.probe(), second.probe());
+        let mut node = AbortableSequence::new("mission".into(), "abort".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        memory.data.insert("abort".into(), Box::new(true));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(first_probe.ticks(), 1);
+        assert_eq!(second_probe.ticks(), 1);
+        assert_eq!(second_probe.resets(), 1);
+        assert!(node.running_child().is_none());
+    }
+
+    #[test]
+    fn test_selector_aborts_mid_run() {
+        let first = MockNode::new("first", vec![NodeResult::Failed]);
+        let second = MockNode::new("second", vec![NodeResult::Running]);
+        let second_probe = second.probe();
+        let mut node = AbortableSelector::new("options".into(), "abort".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        memory.data.insert("abort".into(), Box::new(true));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(second_probe.resets(), 1);
+
+        memory.data.insert("abort".into(), Box::new(false));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_abort_halts_running_child() {
+        let halts = Arc::new(AtomicUsize::new(0));
+        let counter = halts.clone();
+        let grasp = Action::with_halt("grasp".into(), |_| NodeResult::Running, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut node = AbortableSequence::new("mission".into(), "abort".into(), vec![Box::new(grasp)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        memory.set("abort", true);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(halts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_sequence_resumes_at_running_child() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running, NodeResult::Passed]);
+        let first_probe = first.probe();
+        let mut node = AbortableSequence::new("mission".into(), "abort".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(first_probe.ticks(), 1);
+    }
+
+    #[test]
+    fn test_selector_resumes_at_running_child() {
+        let shield = MockNode::new("shield", vec![NodeResult::Failed, NodeResult::Passed]);
+        let grasp = MockNode::new("grasp", vec![NodeResult::Running, NodeResult::Passed]);
+        let (shield_probe, grasp_probe) = (shield.probe(), grasp.probe());
+        let mut node = AbortableSelector::new("options".into(), "abort".into(), vec![Box::new(shield), Box::new(grasp)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!((shield_probe.ticks(), grasp_probe.ticks()), (1, 2));
+        assert_eq!(grasp_probe.resets(), 0);
+    }
+}
@@ -1,25 +1,181 @@
 use crate::{blackboard::BlackBoard, 
-    node::{Node, NodeResult}};
+    config::NodeConfig,
+    node::{Node, NodeId, NodeResult}};
 
+pub type ActionFn = Box<dyn FnMut(&mut BlackBoard) -> NodeResult + Send + Sync>;
+pub type HaltFn = Box<dyn FnMut() + Send + Sync>;
+
+/// A leaf running a closure against the blackboard. The closure may capture
+/// its own configuration and state, e.g. `move |bb| { count += 1; ... }`.
+/// An optional halt closure runs when the action is reset or interrupted
+/// while it is still running.
 pub struct Action {
-    pub action: fn(&mut BlackBoard) -> NodeResult,
-    pub name: String
+    pub action: ActionFn,
+    pub halt: Option<HaltFn>,
+    pub id: NodeId,
+    pub name: String,
+    running: bool
 }
 
 impl Action {
-    pub fn new(name: String, action: fn(&mut BlackBoard) -> NodeResult) -> Self {
-        Self { action, name}
+    pub fn new(name: String, action: impl FnMut(&mut BlackBoard) -> NodeResult + Send + Sync + 'static) -> Self {
+        Self { action: Box::new(action), halt: None, id: NodeId::next(), name, running: false }
+    }
+
+    pub fn with_halt(
+        name: String,
+        action: impl FnMut(&mut BlackBoard) -> NodeResult + Send + Sync + 'static,
+        halt: impl FnMut() + Send + Sync + 'static
+    ) -> Self {
+        Self { halt: Some(Box::new(halt)), ..Self::new(name, action) }
+    }
+
+    pub fn with_id(mut self, id: NodeId) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn from_fn(name: String, action: fn(&mut BlackBoard) -> NodeResult) -> Self {
+        Self::new(name, action)
+    }
+
+    /// Rebuilds an action from its config. The closure itself cannot be
+    /// stored, so the caller supplies it again.
+    pub fn from_config(config: &NodeConfig, action: impl FnMut(&mut BlackBoard) -> NodeResult + Send + Sync + 'static) -> Result<Self, String> {
+        config.expect_kind("Action")?;
+        Ok(Self::new(config.name.clone(), action))
     }
 }
 
 impl Node for Action {
     fn tick(&mut self, blackboard: &mut BlackBoard) -> NodeResult {
-        (self.action)(blackboard)
+        let result = (self.action)(blackboard);
+        self.running = result == NodeResult::Running;
+        result
+    }
+
+    fn reset(&mut self) {
+        self.on_halt();
+    }
+
+    fn on_halt(&mut self) {
+        if std::mem::take(&mut self.running)
+            && let Some(halt) = self.halt.as_mut() {
+            halt();
+        }
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
     }
 
-    fn reset(&mut self) { }
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Action".into(), self.name.clone())
+    }
 
     fn get_name(&self) -> String {
         self.name.clone()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeId, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::inverter::Inverter;
+    use crate::nodes::sequence::Sequence;
+
+    #[test]
+    fn test_closure_keeps_captured_state() {
+        let mut count = 0_u32;
+        let step = 5;
+        let mut node = Action::new("count".into(), move |bb| {
+            count += step;
+            bb.set("count", count);
+            NodeResult::Passed
+        });
+        let mut memory = BlackBoard::new();
+
+        for expected in [5_u32, 10, 15] {
+            assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+            assert_eq!(memory.get::<u32>("count"), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn test_from_fn() {
+        fn idle(_: &mut BlackBoard) -> NodeResult {
+            NodeResult::Running
+        }
+        let mut node = Action::from_fn("idle".into(), idle);
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_same_names_get_distinct_ids() {
+        let first = Action::new("attack".into(), |_| NodeResult::Passed);
+        let second = Action::new("attack".into(), |_| NodeResult::Passed);
+        assert_ne!(first.id(), second.id());
+
+        let pinned = Action::new("attack".into(), |_| NodeResult::Passed).with_id(NodeId(7));
+        assert_eq!(pinned.id(), NodeId(7));
+    }
+
+    #[test]
+    fn test_id_survives_moves() {
+        let guard = Inverter::new(Box::new(Action::new("attack".into(), |_| NodeResult::Passed)), "not_attack".into());
+        let id = guard.id();
+        let boxed: Box<dyn Node> = Box::new(guard);
+        assert_eq!(boxed.id(), id);
+        assert_ne!(boxed.children()[0].id(), id);
+    }
+
+    #[test]
+    fn test_halt_fires_once_when_running_action_is_reset() {
+        let halts = Arc::new(AtomicUsize::new(0));
+        let counter = halts.clone();
+        let mut node = Action::with_halt("grasp".into(), |_| NodeResult::Running, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut memory = BlackBoard::new();
+
+        node.reset();
+        assert_eq!(halts.load(Ordering::SeqCst), 0);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        node.reset();
+        node.reset();
+        assert_eq!(halts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_halt_skipped_after_completion() {
+        let halts = Arc::new(AtomicUsize::new(0));
+        let counter = halts.clone();
+        let mut node = Action::with_halt("grasp".into(), |_| NodeResult::Passed, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+        node.reset();
+        assert_eq!(halts.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_aborted_sequence_halts_running_action() {
+        let halts = Arc::new(AtomicUsize::new(0));
+        let counter = halts.clone();
+        let grasp = Action::with_halt("grasp".into(), |_| NodeResult::Running, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut node = Sequence::new("pick".into(), vec![Box::new(grasp)]);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Running);
+        node.interrupt();
+        assert_eq!(halts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, running_path, Trace, tick_child};
+
+/// Writes the names along the currently running path of its child to a
+/// blackboard key after every tick, e.g. `"root > patrol > walk"`.
+/// The key holds an empty string whenever the child is not running.
+pub struct Breadcrumb {
+    pub child: Box<dyn Node>,
+    pub key: String,
+    pub separator: String,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Breadcrumb {
+    pub fn new(name: String, key: String, child: Box<dyn Node>) -> Self {
+        Self { child, key, separator: " > ".into(), id: NodeId::next(), name }
+    }
+
+    pub fn with_separator(mut self, separator: String) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        let path = match result {
+            NodeResult::Running => running_path(self.child.as_ref()).join(&self.separator),
+            _ => String::new()
+        };
+        memory.set(&self.key, path);
+        result
+    }
+}
+
+impl Node for Breadcrumb {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::breadcrumb::Breadcrumb;
+    use crate::nodes::inverter::Inverter;
+    use crate::nodes::sequence::Sequence;
+
+    fn breadcrumb(memory: &BlackBoard) -> String {
+        memory.data.get("breadcrumb")
+            .and_then(|value| value.downcast_ref::<String>())
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_breadcrumb_follows_running_path() {
+        let patrol = Sequence::new("patrol".into(), vec![
+            Box::new(Action::new("look".into(), |_| NodeResult::Passed)),
+            Box::new(Action::new("walk".into(), |_| NodeResult::Running)),
+        ]);
+        let root = Sequence::new("root".into(), vec![
+            Box::new(Inverter::new(Box::new(Action::new("enemy".into(), |_| NodeResult::Failed)), "no_enemy".into())),
+            Box::new(patrol),
+        ]);
+        let mut node = Breadcrumb::new("trail".into(), "breadcrumb".into(), Box::new(root));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(breadcrumb(&memory), "root > patrol > walk");
+        assert!(memory.key_version("breadcrumb") > 0);
+    }
+
+    #[test]
+    fn test_breadcrumb_cleared_when_finished() {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(Action::new("done".into(), |_| NodeResult::Passed)),
+        ]);
+        let mut node = Breadcrumb::new("trail".into(), "breadcrumb".into(), Box::new(root));
+        let mut memory = BlackBoard::new();
+        memory.data.insert("breadcrumb".into(), Box::new(String::from("stale")));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(breadcrumb(&memory), "");
+    }
+}
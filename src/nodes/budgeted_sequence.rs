@@ -0,0 +1,240 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// What a `BudgetedSequence` does when a child runs past its budget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverrunPolicy {
+    /// Fail the whole sequence.
+    Fail,
+    /// Treat the child as done and move on to the next one.
+    Skip
+}
+
+/// A sequence that gives every child its own time budget, measured from the
+/// tick the child first ran. Children use `default_budget` unless overridden
+/// with `with_budget`. A child still running once its budget is spent is
+/// reset and handled according to the `OverrunPolicy`. The sequence resumes
+/// at the running child instead of starting over.
+pub struct BudgetedSequence {
+    pub children: Vec<Box<dyn Node>>,
+    pub budgets: Vec<Duration>,
+    pub policy: OverrunPolicy,
+    pub current_child: Option<(usize, Instant)>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl BudgetedSequence {
+    pub fn new(name: String, default_budget: Duration, children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            budgets: vec![default_budget; children.len()],
+            children,
+            policy: OverrunPolicy::Fail,
+            current_child: None,
+            suspended_at: None,
+            clock: Arc::new(SystemClock),
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    /// Sets the budget of the child at `index`. An index past the last child
+    /// is ignored.
+    pub fn with_budget(mut self, index: usize, budget: Duration) -> Self {
+        if let Some(slot) = self.budgets.get_mut(index) {
+            *slot = budget;
+        }
+        self
+    }
+
+    pub fn with_policy(mut self, policy: OverrunPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let resume = self.current_child.take();
+        for index in resume.map_or(0, |(index, _)| index)..self.children.len() {
+            let started = match resume {
+                Some((running, started)) if running == index => started,
+                _ => now
+            };
+            if now.duration_since(started) >= self.budgets[index] {
+                self.children[index].reset();
+                match self.policy {
+                    OverrunPolicy::Fail => return NodeResult::Failed,
+                    OverrunPolicy::Skip => continue
+                }
+            }
+            match tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Passed => continue,
+                NodeResult::Running => {
+                    self.current_child = Some((index, started));
+                    return NodeResult::Running;
+                },
+                NodeResult::Failed => return NodeResult::Failed
+            }
+        }
+        NodeResult::Passed
+    }
+}
+
+impl Node for BudgetedSequence {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+        self.suspended_at = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|(index, _)| self.children[index].as_ref())
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        for child in &mut self.children {
+            child.suspend(memory);
+        }
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some((_, started)), Some(suspended_at)) = (&mut self.current_child, self.suspended_at.take()) {
+            *started += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        for child in &mut self.children {
+            child.resume(memory);
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::budgeted_sequence::{BudgetedSequence, OverrunPolicy};
+    use crate::tests::{MockNode, context_at};
+
+    fn steps() -> (MockNode, MockNode, MockNode) {
+        (
+            MockNode::new("approach", vec![NodeResult::Running, NodeResult::Passed]),
+            MockNode::new("grab", vec![NodeResult::Running]),
+            MockNode::new("leave", vec![NodeResult::Passed])
+        )
+    }
+
+    #[test]
+    fn test_overrun_fails_sequence() {
+        let clock = ManualClock::new();
+        let (approach, grab, leave) = steps();
+        let (grab_probe, leave_probe) = (grab.probe(), leave.probe());
+        let mut node = BudgetedSequence::new("fetch".into(), Duration::from_secs(10), vec![Box::new(approach), Box::new(grab), Box::new(leave)])
+            .with_budget(1, Duration::from_secs(2))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(grab_probe.ticks(), 2);
+        assert_eq!(grab_probe.resets(), 1);
+        assert_eq!(leave_probe.ticks(), 0);
+    }
+
+    #[test]
+    fn test_overrun_skips_child() {
+        let clock = ManualClock::new();
+        let (approach, grab, leave) = steps();
+        let leave_probe = leave.probe();
+        let mut node = BudgetedSequence::new("fetch".into(), Duration::from_secs(2), vec![Box::new(approach), Box::new(grab), Box::new(leave)])
+            .with_policy(OverrunPolicy::Skip)
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(leave_probe.ticks(), 1);
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let (_, grab, _) = steps();
+        let mut node = BudgetedSequence::new("fetch".into(), Duration::from_secs(2), vec![Box::new(grab)])
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_out_of_range_budget_is_ignored() {
+        let (approach, grab, leave) = steps();
+        let node = BudgetedSequence::new("fetch".into(), Duration::from_secs(10), vec![Box::new(approach), Box::new(grab), Box::new(leave)])
+            .with_budget(3, Duration::from_secs(2));
+
+        assert_eq!(node.budgets, vec![Duration::from_secs(10); 3]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::node::{Node, NodeId, NodeResult};
+
+/// When a `CachedCondition` evaluates its predicate again.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Invalidation {
+    /// Whenever the blackboard entry under the key has changed, see
+    /// `BlackBoard::key_version`.
+    OnChange(String),
+    /// Once the cached answer is older than the duration.
+    After(Duration)
+}
+
+/// A condition for expensive predicates such as raycasts: the answer is
+/// kept and returned as is until `invalidation` says it is stale, instead of
+/// evaluating the predicate on every tick. `reset` drops the cached answer.
+pub struct CachedCondition {
+    pub predicate: Box<dyn FnMut(&BlackBoard) -> bool + Send + Sync>,
+    pub invalidation: Invalidation,
+    pub cached: Option<(bool, Instant, u64)>,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl CachedCondition {
+    pub fn new(name: String, predicate: impl FnMut(&BlackBoard) -> bool + Send + Sync + 'static, invalidation: Invalidation) -> Self {
+        Self {
+            predicate: Box::new(predicate),
+            invalidation,
+            cached: None,
+            clock: Arc::new(SystemClock),
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn is_fresh(&self, now: Instant, memory: &BlackBoard) -> Option<bool> {
+        let (answer, at, version) = self.cached?;
+        let fresh = match &self.invalidation {
+            Invalidation::OnChange(key) => memory.key_version(key) == version,
+            Invalidation::After(ttl) => now.duration_since(at) < *ttl
+        };
+        fresh.then_some(answer)
+    }
+}
+
+impl Node for CachedCondition {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let answer = match self.is_fresh(now, memory) {
+            Some(answer) => answer,
+            None => {
+                let answer = (self.predicate)(memory);
+                let version = match &self.invalidation {
+                    Invalidation::OnChange(key) => memory.key_version(key),
+                    Invalidation::After(_) => 0
+                };
+                self.cached = Some((answer, now, version));
+                answer
+            }
+        };
+        match answer {
+            true => NodeResult::Passed,
+            false => NodeResult::Failed
+        }
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.cached = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::cached_condition::{CachedCondition, Invalidation};
+    use crate::tests::context_at;
+
+    fn line_of_sight(calls: &Arc<AtomicUsize>, invalidation: Invalidation) -> CachedCondition {
+        let calls = calls.clone();
+        CachedCondition::new("line_of_sight".into(), move |bb| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            bb.get::<f64>("distance").is_some_and(|distance| *distance < 10.0)
+        }, invalidation)
+    }
+
+    #[test]
+    fn test_recomputes_when_watched_key_changes() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut node = line_of_sight(&calls, Invalidation::OnChange("distance".into()));
+        let mut memory = BlackBoard::new();
+        memory.set("distance", 5.0);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        memory.set("noise", 1_u32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        memory.set("distance", 20.0);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_recomputes_after_ttl() {
+        let clock = ManualClock::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut node = line_of_sight(&calls, Invalidation::After(Duration::from_millis(500)))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+        memory.set("distance", 5.0);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        memory.set("distance", 20.0);
+        clock.advance(Duration::from_millis(400));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut node = line_of_sight(&calls, Invalidation::After(Duration::from_secs(2))).with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+        memory.set("distance", 5.0_f64);
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Passed);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Passed);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Passed);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::nodes::sequence::Sequence;
+
+/// The sequence that remembers which children already passed, under the
+/// name it was first added as. `Sequence` resumes at its running child, so
+/// the children that passed before it are not run again until the sequence
+/// finishes or is reset; there is nothing left to cache on top of that.
+pub type CachingSequence = Sequence;
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::caching_sequence::CachingSequence;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_passed_children_are_skipped() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+        let (first_probe, second_probe) = (first.probe(), second.probe());
+        let mut node = CachingSequence::new("cached".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(first_probe.ticks(), 1);
+        assert_eq!(second_probe.ticks(), 3);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(first_probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_reset_clears_memory() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running]);
+        let first_probe = first.probe();
+        let mut node = CachingSequence::new("cached".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        node.tick(&mut memory);
+        node.tick(&mut memory);
+        assert_eq!(first_probe.ticks(), 1);
+
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(first_probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_failure_clears_memory() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running, NodeResult::Failed, NodeResult::Passed]);
+        let first_probe = first.probe();
+        let mut node = CachingSequence::new("cached".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(first_probe.ticks(), 2);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::blackboard::BlackBoard;
+use crate::config::NodeConfig;
+use crate::node::{Node, NodeId, NodeResult};
+
+/// A leaf that checks `predicate` against the blackboard: `Passed` when it
+/// holds, `Failed` otherwise. Conditions never return `Running`.
+///
+/// The predicate may update the blackboard, e.g. to count checks, and may
+/// capture values computed at build time, such as a threshold: `move |bb| bb.get::<f64>("battery").is_some_and(|b| *b > threshold)`.
+pub struct Condition {
+    pub predicate: Box<dyn FnMut(&mut BlackBoard) -> bool + Send + Sync>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Condition {
+    pub fn new(name: String, predicate: impl FnMut(&mut BlackBoard) -> bool + Send + Sync + 'static) -> Self {
+        Self { predicate: Box::new(predicate), id: NodeId::next(), name }
+    }
+
+    pub fn with_id(mut self, id: NodeId) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn from_fn(name: String, predicate: fn(&mut BlackBoard) -> bool) -> Self {
+        Self::new(name, predicate)
+    }
+}
+
+impl Node for Condition {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        if (self.predicate)(memory) {
+            NodeResult::Passed
+        } else {
+            NodeResult::Failed
+        }
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Condition".into(), self.name.clone())
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn reset(&mut self) { }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::condition::Condition;
+
+    #[test]
+    fn test_result_follows_predicate() {
+        let mut node: Box<dyn Node> = Box::new(Condition::new("low_health".into(), |bb| {
+            bb.get::<i32>("health").is_some_and(|health| *health < 30)
+        }));
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.get_name(), "low_health");
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+
+        memory.set("health", 20_i32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+
+        memory.set("health", 90_i32);
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_captured_thresholds() {
+        let battery_above = |threshold: f64| move |bb: &mut BlackBoard| {
+            bb.get::<f64>("battery").copied().unwrap_or(0.0) > threshold
+        };
+        let mut low = Condition::new("battery_ok".into(), battery_above(0.2));
+        let mut high = Condition::new("battery_full".into(), battery_above(0.9));
+        let mut memory = BlackBoard::new();
+        memory.set("battery", 0.5_f64);
+
+        assert_eq!(low.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(high.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        fn always(_: &mut BlackBoard) -> bool {
+            true
+        }
+        let mut node = Condition::from_fn("always".into(), always);
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_predicate_can_update_memory() {
+        let mut node = Condition::new("first_sighting".into(), |bb| {
+            let seen = bb.get::<u32>("sightings").copied().unwrap_or(0);
+            bb.set("sightings", seen + 1);
+            seen == 0
+        });
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(memory.get::<u32>("sightings"), Some(&2));
+    }
+}
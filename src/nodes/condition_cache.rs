@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Blackboard key holding the per-tick results while a `ConditionCache` ticks.
+pub const CONDITION_CACHE_KEY: &str = "condition_cache";
+
+type ResultCache = HashMap<NodeId, NodeResult>;
+
+/// A condition that can be placed in several branches at once. Clones share
+/// the wrapped node and its id, so under a `ConditionCache` the condition is
+/// ticked at most once per tick and every other clone reuses that result.
+/// Outside a cache every clone ticks the condition as usual.
+#[derive(Clone)]
+pub struct SharedCondition {
+    pub condition: Arc<Mutex<Box<dyn Node>>>,
+    pub id: NodeId
+}
+
+impl SharedCondition {
+    pub fn new(condition: Box<dyn Node>) -> Self {
+        Self {
+            condition: Arc::new(Mutex::new(condition)),
+            id: NodeId::next()
+        }
+    }
+}
+
+impl Node for SharedCondition {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        let cached = memory.get::<ResultCache>(CONDITION_CACHE_KEY)
+            .and_then(|cache| cache.get(&self.id()))
+            .cloned();
+        if let Some(result) = cached {
+            return result;
+        }
+
+        let result = self.condition.lock().unwrap().tick(memory);
+        memory.update::<ResultCache>(CONDITION_CACHE_KEY, |cache| {
+            cache.insert(self.id, result.clone());
+        });
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.condition.lock().unwrap().get_name()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.condition.lock().unwrap().last_error()
+    }
+
+    fn reset(&mut self) {
+        self.condition.lock().unwrap().reset();
+    }
+}
+
+/// Gives its subtree a fresh `SharedCondition` cache for the duration of
+/// each tick, so guards repeated across many branches are evaluated once.
+/// Results are keyed by the condition's `Node::id`. A cache nested inside
+/// another one reuses the outer cache, and only the outermost takes it off
+/// the blackboard again when its tick returns.
+pub struct ConditionCache {
+    pub child: Box<dyn Node>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl ConditionCache {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        if memory.get::<ResultCache>(CONDITION_CACHE_KEY).is_some() {
+            return tick_child(self.child.as_mut(), memory, &mut trace);
+        }
+        memory.set(CONDITION_CACHE_KEY, ResultCache::new());
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        memory.remove(CONDITION_CACHE_KEY);
+        result
+    }
+}
+
+impl Node for ConditionCache {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::condition_cache::{CONDITION_CACHE_KEY, ConditionCache, SharedCondition};
+    use crate::nodes::selector::Selector;
+    use crate::nodes::sequence::Sequence;
+    use crate::tests::MockNode;
+
+    fn branches(guard: &SharedCondition) -> Box<dyn Node> {
+        Box::new(Selector::new("options".into(), vec![
+            Box::new(Sequence::new("attack".into(), vec![
+                Box::new(guard.clone()),
+                Box::new(MockNode::new("swing", vec![NodeResult::Failed])),
+            ])),
+            Box::new(Sequence::new("chase".into(), vec![
+                Box::new(guard.clone()),
+                Box::new(MockNode::new("run", vec![NodeResult::Passed])),
+            ])),
+        ]))
+    }
+
+    #[test]
+    fn test_shared_condition_runs_once_per_tick() {
+        let enemy = MockNode::new("enemy_visible", vec![NodeResult::Passed]);
+        let probe = enemy.probe();
+        let guard = SharedCondition::new(Box::new(enemy));
+        let mut node = ConditionCache::new("cache".into(), branches(&guard));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+        assert!(!memory.contains_key(CONDITION_CACHE_KEY));
+    }
+
+    #[test]
+    fn test_nested_caches_share_results() {
+        let enemy = MockNode::new("enemy_visible", vec![NodeResult::Passed]);
+        let probe = enemy.probe();
+        let guard = SharedCondition::new(Box::new(enemy));
+        let mut node = ConditionCache::new("outer".into(), Box::new(Sequence::new("plan".into(), vec![
+            Box::new(guard.clone()),
+            Box::new(ConditionCache::new("inner".into(), branches(&guard))),
+            Box::new(guard.clone()),
+        ])));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 1);
+        assert!(!memory.contains_key(CONDITION_CACHE_KEY));
+    }
+
+    #[test]
+    fn test_without_cache_ticks_every_reference() {
+        let enemy = MockNode::new("enemy_visible", vec![NodeResult::Passed]);
+        let probe = enemy.probe();
+        let guard = SharedCondition::new(Box::new(enemy));
+        let mut node = branches(&guard);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Fails without ticking its child while less than `period` has passed since
+/// the child last returned `Passed`. Outside that window the child is ticked
+/// normally. `reset` clears the cooldown.
+pub struct Cooldown {
+    pub child: Box<dyn Node>,
+    pub period: Duration,
+    pub last_success: Option<Instant>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Cooldown {
+    pub fn new(name: String, period: Duration, child: Box<dyn Node>) -> Self {
+        Self { child, period, last_success: None, suspended_at: None, clock: Arc::new(SystemClock), id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("Cooldown")?;
+        Ok(Self::new(config.name.clone(), config.duration("period")?, child))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        if self.last_success.is_some_and(|last| now.duration_since(last) < self.period) {
+            return NodeResult::Failed;
+        }
+
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        if result == NodeResult::Passed {
+            self.last_success = Some(now);
+        }
+        result
+    }
+}
+
+impl Node for Cooldown {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Cooldown".into(), self.name.clone())
+            .with("period", ConfigValue::Duration(self.period))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.last_success = None;
+        self.suspended_at = None;
+        self.child.reset();
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        self.child.suspend(memory);
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some(last), Some(suspended_at)) = (&mut self.last_success, self.suspended_at.take()) {
+            *last += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        self.child.resume(memory);
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::cooldown::Cooldown;
+    use crate::tests::{MockNode, context_at};
+
+    #[test]
+    fn test_gate_before_and_after_window() {
+        let clock = ManualClock::new();
+        let fire = MockNode::new("fire", vec![NodeResult::Passed]);
+        let probe = fire.probe();
+        let mut node = Cooldown::new("reload".into(), Duration::from_secs(2), Box::new(fire))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 1);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_failures_do_not_start_cooldown() {
+        let clock = ManualClock::new();
+        let fire = MockNode::new("fire", vec![NodeResult::Failed, NodeResult::Passed]);
+        let mut node = Cooldown::new("reload".into(), Duration::from_secs(2), Box::new(fire))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+
+        node.reset();
+        assert!(node.last_success.is_none());
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let fire = MockNode::new("fire", vec![NodeResult::Passed]);
+        let mut node = Cooldown::new("reload".into(), Duration::from_secs(2), Box::new(fire))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Passed);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Failed);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Passed);
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Returns `Running` for `delay` after its first tick and only then starts
+/// ticking its child, passing its result through, e.g. wait 2s, then open
+/// the door. Unlike `Timeout` this gates the start, not the deadline. The
+/// timer is cleared whenever the child finishes, so every run waits again.
+pub struct Delay {
+    pub child: Box<dyn Node>,
+    pub delay: Duration,
+    pub start: Option<Instant>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Delay {
+    pub fn new(name: String, delay: Duration, child: Box<dyn Node>) -> Self {
+        Self {
+            child,
+            delay,
+            start: None,
+            suspended_at: None,
+            clock: Arc::new(SystemClock),
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("Delay")?;
+        Ok(Self::new(config.name.clone(), config.duration("delay")?, child))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let start = *self.start.get_or_insert(now);
+        if now.duration_since(start) < self.delay {
+            return NodeResult::Running;
+        }
+
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        if result != NodeResult::Running {
+            self.start = None;
+        }
+        result
+    }
+}
+
+impl Node for Delay {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Delay".into(), self.name.clone())
+            .with("delay", ConfigValue::Duration(self.delay))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.start = None;
+        self.suspended_at = None;
+        self.child.reset();
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        self.child.suspend(memory);
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some(start), Some(suspended_at)) = (&mut self.start, self.suspended_at.take()) {
+            *start += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        self.child.resume(memory);
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::delay::Delay;
+    use crate::tests::context_at;
+
+    fn delayed_door(clock: &ManualClock) -> Delay {
+        let open_door = Action::new("open_door".into(), |bb| {
+            bb.set("door_opened", true);
+            NodeResult::Passed
+        });
+        Delay::new("wait_then_open".into(), Duration::from_secs(2), Box::new(open_door))
+            .with_clock(Arc::new(clock.clone()))
+    }
+
+    #[test]
+    fn test_child_waits_for_delay() {
+        let clock = ManualClock::new();
+        let mut node = delayed_door(&clock);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_millis(1999));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert!(!memory.contains_key("door_opened"));
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<bool>("door_opened"), Some(&true));
+    }
+
+    #[test]
+    fn test_reset_restarts_delay() {
+        let clock = ManualClock::new();
+        let mut node = delayed_door(&clock);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(3));
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert!(!memory.contains_key("door_opened"));
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let mut node = delayed_door(&clock);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Passed);
+        assert_eq!(memory.get::<bool>("door_opened"), Some(&true));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Stores the message of a child's error under `key` and reports a plain
+/// `Failed`, so the tree keeps running while the diagnostic is preserved.
+/// The key is left untouched on ticks without an error, so it always holds
+/// the most recent one.
+pub struct ErrorCapture {
+    pub child: Box<dyn Node>,
+    pub key: String,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl ErrorCapture {
+    pub fn new(name: String, key: String, child: Box<dyn Node>) -> Self {
+        Self { child, key, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        if result == NodeResult::Failed
+            && let Some(error) = self.child.last_error() {
+            memory.set(&self.key, error);
+        }
+        result
+    }
+}
+
+impl Node for ErrorCapture {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::error_capture::ErrorCapture;
+    use crate::nodes::fallible_action::FallibleAction;
+    use crate::nodes::sequence::Sequence;
+
+    fn connect(memory: &mut BlackBoard) -> Result<NodeResult, String> {
+        match memory.data.get("online").and_then(|value| value.downcast_ref::<bool>()) {
+            Some(true) => Ok(NodeResult::Passed),
+            _ => Err("link down".into())
+        }
+    }
+
+    fn message(memory: &BlackBoard) -> Option<String> {
+        memory.data.get("error").and_then(|value| value.downcast_ref::<String>()).cloned()
+    }
+
+    #[test]
+    fn test_error_is_captured() {
+        let capture = ErrorCapture::new("capture".into(), "error".into(), Box::new(FallibleAction::new("connect".into(), connect)));
+        let mut root = Sequence::new("root".into(), vec![Box::new(capture)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(root.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(message(&memory).as_deref(), Some("link down"));
+        assert!(memory.key_version("error") > 0);
+        assert!(root.children[0].last_error().is_none());
+    }
+
+    #[test]
+    fn test_no_error_leaves_key_alone() {
+        let mut node = ErrorCapture::new("capture".into(), "error".into(), Box::new(FallibleAction::new("connect".into(), connect)));
+        let mut memory = BlackBoard::new();
+        memory.data.insert("online".into(), Box::new(true));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(message(&memory), None);
+    }
+}
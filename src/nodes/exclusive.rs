@@ -0,0 +1,174 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+static NEXT_OWNER: AtomicU64 = AtomicU64::new(1);
+
+/// Lets only one of the decorators sharing `lock` have a running child.
+/// The lock lives on the blackboard under `mutex/<lock>`; every other
+/// decorator with the same lock fails without ticking its child until the
+/// holder's child passes or fails, or the holder is reset or interrupted.
+pub struct Exclusive {
+    pub child: Box<dyn Node>,
+    pub lock: String,
+    pub owner: u64,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String,
+    held: Option<Arc<AtomicU64>>
+}
+
+impl Exclusive {
+    pub fn new(name: String, lock: String, child: Box<dyn Node>) -> Self {
+        Self { child, lock, owner: NEXT_OWNER.fetch_add(1, Ordering::Relaxed), error: None, id: NodeId::next(), name, held: None }
+    }
+
+    fn lock_key(&self) -> String {
+        format!("mutex/{}", self.lock)
+    }
+
+    fn release(&mut self) {
+        if let Some(holder) = self.held.take() {
+            let _ = holder.compare_exchange(self.owner, 0, Ordering::SeqCst, Ordering::SeqCst);
+        }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let holder = memory.entry_or_insert_with(&self.lock_key(), || Arc::new(AtomicU64::new(0))).clone();
+        let current = holder.load(Ordering::SeqCst);
+        if current != 0 && current != self.owner {
+            return NodeResult::Failed;
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        if result == NodeResult::Running {
+            holder.store(self.owner, Ordering::SeqCst);
+            self.held = Some(holder);
+        } else {
+            self.held = Some(holder);
+            self.release();
+        }
+        result
+    }
+}
+
+impl Node for Exclusive {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.release();
+        self.child.reset();
+    }
+
+    fn on_halt(&mut self) {
+        self.release();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::condition::Condition;
+    use crate::nodes::exclusive::Exclusive;
+    use crate::nodes::reactive_sequence::ReactiveSequence;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_only_one_holder_runs() {
+        let drive = MockNode::new("drive", vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+        let charge = MockNode::new("charge", vec![NodeResult::Running]);
+        let charge_probe = charge.probe();
+        let mut driving = Exclusive::new("driving".into(), "motors".into(), Box::new(drive));
+        let mut charging = Exclusive::new("charging".into(), "motors".into(), Box::new(charge));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(driving.tick(&mut memory), NodeResult::Running);
+        assert_eq!(charging.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(driving.tick(&mut memory), NodeResult::Running);
+        assert_eq!(charging.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(charge_probe.ticks(), 0);
+
+        assert_eq!(driving.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(charging.tick(&mut memory), NodeResult::Running);
+        assert_eq!(charge_probe.ticks(), 1);
+        assert_eq!(driving.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_different_locks_are_independent() {
+        let mut first = Exclusive::new("first".into(), "a".into(), Box::new(MockNode::new("x", vec![NodeResult::Running])));
+        let mut second = Exclusive::new("second".into(), "b".into(), Box::new(MockNode::new("y", vec![NodeResult::Running])));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(first.tick(&mut memory), NodeResult::Running);
+        assert_eq!(second.tick(&mut memory), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_interrupted_holder_releases_lock() {
+        let drive = Exclusive::new("driving".into(), "motors".into(), Box::new(MockNode::new("drive", vec![NodeResult::Running])));
+        let guard = Condition::new("may_drive".into(), |bb| bb.get::<bool>("may_drive").copied().unwrap_or(true));
+        let mut driving = ReactiveSequence::new("drive_when_allowed".into(), vec![Box::new(guard), Box::new(drive)]);
+        let mut charging = Exclusive::new("charging".into(), "motors".into(), Box::new(MockNode::new("charge", vec![NodeResult::Running])));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(driving.tick(&mut memory), NodeResult::Running);
+        assert_eq!(charging.tick(&mut memory), NodeResult::Failed);
+
+        memory.set("may_drive", false);
+        assert_eq!(driving.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(charging.tick(&mut memory), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_reset_releases_lock() {
+        let mut driving = Exclusive::new("driving".into(), "motors".into(), Box::new(MockNode::new("drive", vec![NodeResult::Running])));
+        let mut charging = Exclusive::new("charging".into(), "motors".into(), Box::new(MockNode::new("charge", vec![NodeResult::Running])));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(driving.tick(&mut memory), NodeResult::Running);
+        driving.reset();
+        assert_eq!(charging.tick(&mut memory), NodeResult::Running);
+        driving.reset();
+        assert_eq!(driving.tick(&mut memory), NodeResult::Failed);
+    }
+}
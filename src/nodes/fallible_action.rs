@@ -0,0 +1,48 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult};
+
+/// An action that can fail with an error message. An `Err` is reported as
+/// `Failed`, with the message kept available through `last_error`.
+pub struct FallibleAction {
+    pub action: fn(&mut BlackBoard) -> Result<NodeResult, String>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl FallibleAction {
+    pub fn new(name: String, action: fn(&mut BlackBoard) -> Result<NodeResult, String>) -> Self {
+        Self { action, error: None, id: NodeId::next(), name }
+    }
+}
+
+impl Node for FallibleAction {
+    fn tick(&mut self, blackboard: &mut BlackBoard) -> NodeResult {
+        match (self.action)(blackboard) {
+            Ok(result) => {
+                self.error = None;
+                result
+            },
+            Err(error) => {
+                self.error = Some(error);
+                NodeResult::Failed
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+}
@@ -0,0 +1,150 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// A selector that does not commit to a running child.
+///
+/// Each tick tries the children in order and a `Running` child is treated
+/// like "not passed yet": the next child is tried as well. The result is
+/// `Passed` as soon as any child passes (children left running on that
+/// tick are reset), `Running` if no child passed but at least one is still
+/// running, and `Failed` only when every child failed.
+pub struct FallthroughSelector {
+    pub children: Vec<Box<dyn Node>>,
+    pub running: Vec<bool>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl FallthroughSelector {
+    pub fn new(name: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            running: vec![false; children.len()],
+            children,
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    fn halt_running(&mut self) {
+        for (child, running) in self.children.iter_mut().zip(&mut self.running) {
+            if *running {
+                child.reset();
+                *running = false;
+            }
+        }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        for index in 0..self.children.len() {
+            match tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Passed => {
+                    self.running[index] = false;
+                    self.halt_running();
+                    return NodeResult::Passed;
+                },
+                result => self.running[index] = result == NodeResult::Running
+            }
+        }
+
+        if self.running.contains(&true) {
+            NodeResult::Running
+        } else {
+            NodeResult::Failed
+        }
+    }
+}
+
+impl Node for FallthroughSelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.running.iter_mut().for_each(|running| *running = false);
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.running.iter().position(|running| *running).map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::fallthrough_selector::FallthroughSelector;
+    use crate::nodes::selector::Selector;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_running_child_does_not_block_later_children() {
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let fast = MockNode::new("fast", vec![NodeResult::Passed]);
+        let (slow_probe, fast_probe) = (slow.probe(), fast.probe());
+        let mut node = FallthroughSelector::new("attempts".into(), vec![Box::new(slow), Box::new(fast)]);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+        assert_eq!(fast_probe.ticks(), 1);
+        assert_eq!(slow_probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_standard_selector_commits_to_running() {
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let fast = MockNode::new("fast", vec![NodeResult::Passed]);
+        let fast_probe = fast.probe();
+        let mut node = Selector::new("attempts".into(), vec![Box::new(slow), Box::new(fast)]);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Running);
+        assert_eq!(fast_probe.ticks(), 0);
+    }
+
+    #[test]
+    fn test_aggregate_result() {
+        let mut memory = BlackBoard::new();
+        let mut node = FallthroughSelector::new("attempts".into(), vec![
+            Box::new(MockNode::new("a", vec![NodeResult::Failed])),
+            Box::new(MockNode::new("b", vec![NodeResult::Running, NodeResult::Failed])),
+        ]);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "b");
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert!(node.running_child().is_none());
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Skips its child while the `Duration` under `budget_key` (the time left in
+/// the current frame, kept up to date by the host) is below `threshold`,
+/// returning `skipped` instead. A child that was running when it gets
+/// skipped is reset. Without a budget on the blackboard the child always runs.
+pub struct FrameBudget {
+    pub child: Box<dyn Node>,
+    pub budget_key: String,
+    pub threshold: Duration,
+    pub skipped: NodeResult,
+    pub is_running: bool,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl FrameBudget {
+    pub fn new(name: String, budget_key: String, threshold: Duration, child: Box<dyn Node>) -> Self {
+        Self { child, budget_key, threshold, skipped: NodeResult::Failed, is_running: false, error: None, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("FrameBudget")?;
+        Ok(Self::new(config.name.clone(), config.text("budget_key")?, config.duration("threshold")?, child)
+            .with_skipped_result(config.result("skipped")?))
+    }
+
+    pub fn with_skipped_result(mut self, result: NodeResult) -> Self {
+        self.skipped = result;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let remaining = memory.get_cloned::<Duration>(&self.budget_key);
+        if remaining.is_some_and(|remaining| remaining < self.threshold) {
+            if self.is_running {
+                self.child.reset();
+                self.is_running = false;
+            }
+            return self.skipped.clone();
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        self.is_running = result == NodeResult::Running;
+        result
+    }
+}
+
+impl Node for FrameBudget {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("FrameBudget".into(), self.name.clone())
+            .with("budget_key", ConfigValue::Text(self.budget_key.clone()))
+            .with("threshold", ConfigValue::Duration(self.threshold))
+            .with("skipped", ConfigValue::Result(self.skipped.clone()))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.is_running = false;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::frame_budget::FrameBudget;
+    use crate::tests::MockNode;
+
+    fn budget(memory: &mut BlackBoard, millis: u64) {
+        memory.data.insert("frame_budget".into(), Box::new(Duration::from_millis(millis)));
+    }
+
+    #[test]
+    fn test_skips_under_pressure() {
+        let child = MockNode::new("pathfind", vec![NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = FrameBudget::new("throttle".into(), "frame_budget".into(), Duration::from_millis(4), Box::new(child))
+            .with_skipped_result(NodeResult::Running);
+        let mut memory = BlackBoard::new();
+
+        budget(&mut memory, 10);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        budget(&mut memory, 2);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.ticks(), 1);
+
+        budget(&mut memory, 4);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+
+        memory.data.remove("frame_budget");
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 3);
+    }
+
+    #[test]
+    fn test_running_child_is_reset_when_skipped() {
+        let child = MockNode::new("pathfind", vec![NodeResult::Running]);
+        let probe = child.probe();
+        let mut node = FrameBudget::new("throttle".into(), "frame_budget".into(), Duration::from_millis(4), Box::new(child));
+        let mut memory = BlackBoard::new();
+
+        budget(&mut memory, 8);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        budget(&mut memory, 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_expired_budget_is_ignored() {
+        let clock = ManualClock::new();
+        let child = MockNode::new("pathfind", vec![NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = FrameBudget::new("throttle".into(), "frame_budget".into(), Duration::from_millis(4), Box::new(child));
+        let mut memory = BlackBoard::new().with_clock(Arc::new(clock.clone()));
+        memory.set_with_ttl("frame_budget", Duration::from_millis(1), Duration::from_secs(1));
+
+        node.tick(&mut memory);
+        assert_eq!(probe.ticks(), 0);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 1);
+    }
+}
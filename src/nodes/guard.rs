@@ -0,0 +1,155 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Checks `condition` on every tick and only ticks its child while it holds,
+/// bundling the usual condition-then-action pair into one node. When the
+/// condition stops holding the guard fails, resetting the child if it was
+/// running, so a running action is interrupted as soon as its
+/// precondition goes away.
+pub struct Guard {
+    pub condition: Box<dyn FnMut(&mut BlackBoard) -> bool + Send + Sync>,
+    pub child: Box<dyn Node>,
+    pub is_running: bool,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Guard {
+    pub fn new(name: String, condition: impl FnMut(&mut BlackBoard) -> bool + Send + Sync + 'static, child: Box<dyn Node>) -> Self {
+        Self { condition: Box::new(condition), child, is_running: false, error: None, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        if !(self.condition)(memory) {
+            if self.is_running {
+                self.is_running = false;
+                self.child.reset();
+            }
+            return NodeResult::Failed;
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        self.is_running = result == NodeResult::Running;
+        result
+    }
+}
+
+impl Node for Guard {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.is_running = false;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.is_running.then_some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::fallible_action::FallibleAction;
+    use crate::nodes::guard::Guard;
+    use crate::tests::MockNode;
+
+    fn has_ammo(memory: &mut BlackBoard) -> bool {
+        memory.get::<u32>("ammo").is_some_and(|ammo| *ammo > 0)
+    }
+
+    #[test]
+    fn test_guard_flip_resets_running_child() {
+        let shoot = MockNode::new("shoot", vec![NodeResult::Running]);
+        let probe = shoot.probe();
+        let mut node = Guard::new("can_shoot".into(), has_ammo, Box::new(shoot));
+        let mut memory = BlackBoard::new();
+        memory.set("ammo", 3_u32);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "shoot");
+
+        memory.set("ammo", 0_u32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(probe.resets(), 1);
+        assert!(node.running_child().is_none());
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_passes_child_result_through() {
+        let reload = MockNode::new("reload", vec![NodeResult::Passed]);
+        let mut node = Guard::new("has_clips".into(), |_| true, Box::new(reload));
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_condition_can_update_memory() {
+        let fire = MockNode::new("fire", vec![NodeResult::Passed]);
+        let mut node = Guard::new("spend_round".into(), |bb| {
+            let ammo = bb.get::<u32>("ammo").copied().unwrap_or(0);
+            bb.set("ammo", ammo.saturating_sub(1));
+            ammo > 0
+        }, Box::new(fire));
+        let mut memory = BlackBoard::new();
+        memory.set("ammo", 1_u32);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(memory.get::<u32>("ammo"), Some(&0));
+    }
+
+    #[test]
+    fn test_error_only_when_child_failed() {
+        fn jammed(_: &mut BlackBoard) -> Result<NodeResult, String> {
+            Err("trigger jammed".into())
+        }
+        let mut node = Guard::new("armed".into(), has_ammo, Box::new(FallibleAction::new("shoot".into(), jammed)));
+        let mut memory = BlackBoard::new();
+        memory.set("ammo", 3_u32);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.last_error().as_deref(), Some("trigger jammed"));
+        memory.set("ammo", 0_u32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.last_error(), None);
+    }
+}
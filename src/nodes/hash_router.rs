@@ -0,0 +1,215 @@
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// 64-bit FNV-1a.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Routes every tick to `children[hash(value) % len]`, where `value` is the
+/// `T` stored under `key`. The same value always picks the same child, so
+/// agents keyed by e.g. an entity id get a stable but varied behavior. The
+/// hash is FNV-1a, which unlike the std hasher never changes between Rust
+/// releases, so saved ids keep their routes.
+/// Fails when the key is missing or expired, holds another type, or there
+/// are no children.
+pub struct HashRouter<T> {
+    pub children: Vec<Box<dyn Node>>,
+    pub key: String,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String,
+    value_type: PhantomData<fn() -> T>
+}
+
+impl<T: Hash + 'static> HashRouter<T> {
+    pub fn new(name: String, key: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            children,
+            key,
+            current_child: None,
+            error: None,
+            id: NodeId::next(),
+            name,
+            value_type: PhantomData
+        }
+    }
+
+    fn route(&self, memory: &BlackBoard) -> Option<usize> {
+        if self.children.is_empty() {
+            return None;
+        }
+        let value = memory.get::<T>(&self.key)?;
+        let mut hasher = Fnv1a(0xcbf29ce484222325);
+        value.hash(&mut hasher);
+        Some((hasher.finish() % self.children.len() as u64) as usize)
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let route = self.route(memory);
+        if let Some(previous) = self.current_child
+            && route != Some(previous) {
+            self.children[previous].reset();
+        }
+
+        let Some(index) = route else {
+            self.current_child = None;
+            return NodeResult::Failed;
+        };
+        let result = tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error);
+        self.current_child = match result {
+            NodeResult::Running => Some(index),
+            _ => None
+        };
+        result
+    }
+}
+
+impl<T: Hash + 'static> Node for HashRouter<T> {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::hash_router::HashRouter;
+
+    fn mark(memory: &mut BlackBoard, branch: usize) -> NodeResult {
+        memory.data.insert("branch".into(), Box::new(branch));
+        NodeResult::Passed
+    }
+
+    fn router() -> HashRouter<u64> {
+        HashRouter::new("router".into(), "entity".into(), vec![
+            Box::new(Action::new("a".into(), |bb| mark(bb, 0))),
+            Box::new(Action::new("b".into(), |bb| mark(bb, 1))),
+            Box::new(Action::new("c".into(), |bb| mark(bb, 2))),
+        ])
+    }
+
+    fn branch_for(node: &mut HashRouter<u64>, entity: u64) -> usize {
+        let mut memory = BlackBoard::new();
+        memory.data.insert("entity".into(), Box::new(entity));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        *memory.data.get("branch").unwrap().downcast_ref::<usize>().unwrap()
+    }
+
+    #[test]
+    fn test_routing_is_stable() {
+        let mut first = router();
+        let mut second = router();
+        for entity in 0..20 {
+            assert_eq!(branch_for(&mut first, entity), branch_for(&mut second, entity));
+            assert_eq!(branch_for(&mut first, entity), branch_for(&mut first, entity));
+        }
+    }
+
+    #[test]
+    fn test_routing_is_distributed() {
+        let mut node = router();
+        let branches: HashSet<usize> = (0..20).map(|entity| branch_for(&mut node, entity)).collect();
+        assert_eq!(branches.len(), 3);
+    }
+
+    #[test]
+    fn test_routes_are_fixed() {
+        let mut node = HashRouter::<String>::new("router".into(), "role".into(), vec![
+            Box::new(Action::new("a".into(), |bb| mark(bb, 0))),
+            Box::new(Action::new("b".into(), |bb| mark(bb, 1))),
+            Box::new(Action::new("c".into(), |bb| mark(bb, 2))),
+        ]);
+        for (role, branch) in [("scout", 1), ("guard", 2), ("medic", 2), ("pilot", 0)] {
+            let mut memory = BlackBoard::new();
+            memory.data.insert("role".into(), Box::new(String::from(role)));
+            node.tick(&mut memory);
+            assert_eq!(memory.data.get("branch").unwrap().downcast_ref::<usize>(), Some(&branch), "{role}");
+        }
+    }
+
+    #[test]
+    fn test_missing_key_fails() {
+        let mut node = router();
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+
+        memory.data.insert("entity".into(), Box::new("wrong type"));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_expired_value_is_not_routed() {
+        let clock = ManualClock::new();
+        let mut node = router();
+        let mut memory = BlackBoard::new().with_clock(Arc::new(clock.clone()));
+        memory.set_with_ttl("entity", 7_u64, Duration::from_secs(1));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+}
@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+pub type TreeParser = Box<dyn Fn(&str) -> Result<Box<dyn Node>, String> + Send + Sync>;
+
+/// Builds its subtree from a tree-definition file and rebuilds it whenever
+/// the file's modification time or size changes, checked at the start of
+/// every tick. The old subtree is interrupted before it is replaced, so a
+/// running action can clean up; the blackboard is untouched. If the file
+/// cannot be read or parsed the previous subtree keeps running and the
+/// error is kept in `load_error`; with no subtree at all the node fails.
+pub struct HotReload {
+    pub path: PathBuf,
+    pub parser: TreeParser,
+    pub child: Option<Box<dyn Node>>,
+    pub version: Option<(SystemTime, u64)>,
+    pub load_error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl HotReload {
+    pub fn new(name: String, path: PathBuf, parser: TreeParser) -> Self {
+        Self { path, parser, child: None, version: None, load_error: None, id: NodeId::next(), name }
+    }
+
+    fn reload_if_changed(&mut self) {
+        let version = match fs::metadata(&self.path).and_then(|meta| Ok((meta.modified()?, meta.len()))) {
+            Ok(version) => version,
+            Err(error) => {
+                self.load_error = Some(format!("cannot stat {}: {error}", self.path.display()));
+                return;
+            }
+        };
+        if self.version == Some(version) {
+            return;
+        }
+        self.version = Some(version);
+
+        let built = fs::read_to_string(&self.path)
+            .map_err(|error| format!("cannot read {}: {error}", self.path.display()))
+            .and_then(|source| (self.parser)(&source));
+        match built {
+            Ok(child) => {
+                if let Some(previous) = &mut self.child {
+                    previous.interrupt();
+                }
+                self.child = Some(child);
+                self.load_error = None;
+            },
+            Err(error) => self.load_error = Some(error)
+        }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.reload_if_changed();
+        match &mut self.child {
+            Some(child) => tick_child(child.as_mut(), memory, &mut trace),
+            None => NodeResult::Failed
+        }
+    }
+}
+
+impl Node for HotReload {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        match &self.child {
+            Some(child) => child.last_error(),
+            None => self.load_error.clone()
+        }
+    }
+
+    fn reset(&mut self) {
+        if let Some(child) = &mut self.child {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.child.as_deref()
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.child.as_deref().into_iter().collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.child.as_deref_mut().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::hot_reload::HotReload;
+    use crate::nodes::sequence::Sequence;
+
+    // One action per line: "pass", "fail" or "count".
+    fn parse(source: &str) -> Result<Box<dyn Node>, String> {
+        let children = source.lines().map(|line| -> Result<Box<dyn Node>, String> {
+            let action: fn(&mut BlackBoard) -> NodeResult = match line.trim() {
+                "pass" => |_| NodeResult::Passed,
+                "fail" => |_| NodeResult::Failed,
+                "count" => |bb| {
+                    let count = bb.data.get("count").and_then(|value| value.downcast_ref::<u32>()).copied().unwrap_or(0);
+                    bb.data.insert("count".into(), Box::new(count + 1));
+                    NodeResult::Passed
+                },
+                other => return Err(format!("unknown action '{other}'"))
+            };
+            Ok(Box::new(Action::new(line.trim().into(), action)))
+        }).collect::<Result<Vec<_>, _>>()?;
+        Ok(Box::new(Sequence::new("root".into(), children)))
+    }
+
+    fn tree_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("neurotree-{}-{name}.tree", std::process::id()))
+    }
+
+    #[test]
+    fn test_reload_on_change() {
+        let path = tree_file("reload");
+        fs::write(&path, "count\nfail\n").unwrap();
+        let mut node = HotReload::new("live".into(), path.clone(), Box::new(parse));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+
+        fs::write(&path, "count\npass\npass\n").unwrap();
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.data.get("count").unwrap().downcast_ref::<u32>(), Some(&2));
+
+        fs::write(&path, "count\nbogus\n").unwrap();
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(node.load_error.as_deref().unwrap().contains("bogus"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file_fails() {
+        let mut node = HotReload::new("live".into(), tree_file("missing"), Box::new(parse));
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+        assert!(node.load_error.is_some());
+        assert_eq!(node.last_error(), node.load_error);
+    }
+
+    #[test]
+    fn test_reload_interrupts_running_tree() {
+        let path = tree_file("interrupt");
+        fs::write(&path, "v1").unwrap();
+        let halts = Arc::new(AtomicUsize::new(0));
+        let counter = halts.clone();
+        let parser = move |_: &str| -> Result<Box<dyn Node>, String> {
+            let counter = counter.clone();
+            Ok(Box::new(Action::with_halt("grasp".into(), |_| NodeResult::Running, move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })))
+        };
+        let mut node = HotReload::new("live".into(), path.clone(), Box::new(parser));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        fs::write(&path, "v2, longer").unwrap();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(halts.load(Ordering::SeqCst), 1);
+        fs::remove_file(&path).unwrap();
+    }
+}
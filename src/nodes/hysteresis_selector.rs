@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// A priority selector that sticks with a running child for at least `dwell`.
+///
+/// Normally every tick re-evaluates the children from the first one, and a
+/// higher-priority child that stops failing takes over from the running one,
+/// which is reset. Once a child is running though, the selector only ticks
+/// that child until `dwell` has passed since it took over, so conditions
+/// flickering near a boundary can't make it switch back and forth. If the
+/// committed child fails, the selector moves on to the children after it.
+pub struct HysteresisSelector {
+    pub children: Vec<Box<dyn Node>>,
+    pub dwell: Duration,
+    pub current_child: Option<(usize, Instant)>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl HysteresisSelector {
+    pub fn new(name: String, dwell: Duration, children: Vec<Box<dyn Node>>) -> Self {
+        Self { children, dwell, current_child: None, suspended_at: None, clock: Arc::new(SystemClock), error: None, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, children: Vec<Box<dyn Node>>) -> Result<Self, String> {
+        config.expect_kind("HysteresisSelector")?;
+        Ok(Self::new(config.name.clone(), config.duration("dwell")?, children))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn select(&mut self, memory: &mut BlackBoard, trace: &mut Option<Trace>, from: usize, now: Instant) -> NodeResult {
+        for index in from..self.children.len() {
+            let committed = self.current_child.filter(|(current, _)| *current == index);
+            let result = tick_child_with_error(self.children[index].as_mut(), memory, trace, &mut self.error);
+            if result == NodeResult::Failed {
+                if committed.is_some() {
+                    self.current_child = None;
+                }
+                continue;
+            }
+
+            if let Some((previous, _)) = self.current_child
+                && previous != index {
+                self.children[previous].reset();
+            }
+            self.current_child = match result {
+                NodeResult::Running => Some(committed.unwrap_or((index, now))),
+                _ => None
+            };
+            return result;
+        }
+        NodeResult::Failed
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        if let Some((index, since)) = self.current_child
+            && now.duration_since(since) < self.dwell {
+            return match tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Running => NodeResult::Running,
+                NodeResult::Passed => {
+                    self.current_child = None;
+                    NodeResult::Passed
+                },
+                NodeResult::Failed => {
+                    self.current_child = None;
+                    self.select(memory, &mut trace, index + 1, now)
+                }
+            };
+        }
+        self.select(memory, &mut trace, 0, now)
+    }
+}
+
+impl Node for HysteresisSelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("HysteresisSelector".into(), self.name.clone())
+            .with("dwell", ConfigValue::Duration(self.dwell))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+        self.suspended_at = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        for child in &mut self.children {
+            child.suspend(memory);
+        }
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some((_, since)), Some(suspended_at)) = (&mut self.current_child, self.suspended_at.take()) {
+            *since += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        for child in &mut self.children {
+            child.resume(memory);
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|(index, _)| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::hysteresis_selector::HysteresisSelector;
+    use crate::tests::{MockNode, context_at};
+
+    #[test]
+    fn test_holds_child_until_dwell_elapses() {
+        let clock = ManualClock::new();
+        let flee = MockNode::new("flee", vec![NodeResult::Failed, NodeResult::Running]);
+        let patrol = MockNode::new("patrol", vec![NodeResult::Running]);
+        let (flee_probe, patrol_probe) = (flee.probe(), patrol.probe());
+        let mut node = HysteresisSelector::new("mode".into(), Duration::from_secs(5), vec![Box::new(flee), Box::new(patrol)])
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        for _ in 0..4 {
+            clock.advance(Duration::from_secs(1));
+            assert_eq!(node.tick(&mut memory), NodeResult::Running);
+            assert_eq!(node.running_child().unwrap().get_name(), "patrol");
+        }
+        assert_eq!(flee_probe.ticks(), 1);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "flee");
+        assert_eq!(patrol_probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_keeps_child_when_priority_unchanged() {
+        let clock = ManualClock::new();
+        let flee = MockNode::new("flee", vec![NodeResult::Failed]);
+        let patrol = MockNode::new("patrol", vec![NodeResult::Running, NodeResult::Running, NodeResult::Failed]);
+        let patrol_probe = patrol.probe();
+        let idle = MockNode::new("idle", vec![NodeResult::Passed]);
+        let mut node = HysteresisSelector::new("mode".into(), Duration::from_secs(5), vec![Box::new(flee), Box::new(patrol), Box::new(idle)])
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(patrol_probe.resets(), 0);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(node.running_child().is_none());
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let flee = MockNode::new("flee", vec![NodeResult::Failed, NodeResult::Passed]);
+        let patrol = MockNode::new("patrol", vec![NodeResult::Running]);
+        let flee_probe = flee.probe();
+        let mut node = HysteresisSelector::new("mode".into(), Duration::from_secs(2), vec![Box::new(flee), Box::new(patrol)])
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Running);
+        assert_eq!(flee_probe.ticks(), 1);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 3)), NodeResult::Passed);
+        assert_eq!(flee_probe.ticks(), 2);
+    }
+}
@@ -0,0 +1,237 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BehaviorRequest {
+    pub behavior: String,
+    pub priority: i32,
+    sequence: u64
+}
+
+impl Ord for BehaviorRequest {
+    // Highest priority first; equal priorities are served first come, first served.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for BehaviorRequest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Pending behavior requests, stored on the blackboard so that systems
+/// outside the tree can enqueue work for an `InboxExecutor`.
+#[derive(Debug, Default)]
+pub struct BehaviorInbox {
+    pub requests: BinaryHeap<BehaviorRequest>,
+    next_sequence: u64
+}
+
+impl BehaviorInbox {
+    pub fn push(&mut self, behavior: &str, priority: i32) {
+        self.requests.push(BehaviorRequest { behavior: behavior.into(), priority, sequence: self.next_sequence });
+        self.next_sequence += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<BehaviorRequest> {
+        self.requests.pop()
+    }
+
+    /// Pushes a request onto the inbox stored under `key`, creating the
+    /// inbox if the key is empty.
+    pub fn enqueue(memory: &mut BlackBoard, key: &str, behavior: &str, priority: i32) {
+        if !memory.update::<BehaviorInbox>(key, |inbox| inbox.push(behavior, priority)) {
+            let mut inbox = BehaviorInbox::default();
+            inbox.push(behavior, priority);
+            memory.set(key, inbox);
+        }
+    }
+}
+
+/// Takes the highest-priority request from the `BehaviorInbox` under `key`
+/// and ticks the matching behavior until it finishes, returning its result,
+/// before looking at the inbox again. Fails when the inbox is empty, and
+/// with a `last_error` naming the behavior when the request is unknown.
+pub struct InboxExecutor {
+    pub behaviors: Vec<(String, Box<dyn Node>)>,
+    pub key: String,
+    pub active: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl InboxExecutor {
+    pub fn new(name: String, key: String, behaviors: Vec<(String, Box<dyn Node>)>) -> Self {
+        Self { behaviors, key, active: None, error: None, id: NodeId::next(), name }
+    }
+
+    fn next_request(&self, memory: &mut BlackBoard) -> Option<BehaviorRequest> {
+        if memory.get::<BehaviorInbox>(&self.key)?.requests.is_empty() {
+            return None;
+        }
+        let mut request = None;
+        memory.update::<BehaviorInbox>(&self.key, |inbox| request = inbox.pop());
+        request
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let index = match self.active {
+            Some(index) => index,
+            None => {
+                let Some(request) = self.next_request(memory) else {
+                    return NodeResult::Failed;
+                };
+                let Some(index) = self.behaviors.iter().position(|(name, _)| *name == request.behavior) else {
+                    self.error = Some(format!("unknown behavior '{}'", request.behavior));
+                    return NodeResult::Failed;
+                };
+                index
+            }
+        };
+
+        let behavior = &mut self.behaviors[index].1;
+        let result = tick_child_with_error(behavior.as_mut(), memory, &mut trace, &mut self.error);
+        if result == NodeResult::Running {
+            self.active = Some(index);
+        } else {
+            behavior.reset();
+            self.active = None;
+        }
+        result
+    }
+}
+
+impl Node for InboxExecutor {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.active = None;
+
+        for (_, behavior) in &mut self.behaviors {
+            behavior.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.active.map(|index| self.behaviors[index].1.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.behaviors.iter().map(|(_, behavior)| behavior.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.behaviors.iter_mut().map(|(_, behavior)| behavior.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::inbox::{BehaviorInbox, InboxExecutor};
+    use crate::tests::MockNode;
+
+    fn log(memory: &mut BlackBoard, entry: &'static str) -> NodeResult {
+        let mut done = memory.data.remove("log")
+            .and_then(|value| value.downcast::<Vec<&'static str>>().ok())
+            .map(|log| *log)
+            .unwrap_or_default();
+        done.push(entry);
+        memory.data.insert("log".into(), Box::new(done));
+        NodeResult::Passed
+    }
+
+    fn executed(memory: &BlackBoard) -> Vec<&'static str> {
+        memory.data.get("log").unwrap().downcast_ref::<Vec<&'static str>>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_requests_run_by_priority() {
+        let mut node = InboxExecutor::new("executor".into(), "inbox".into(), vec![
+            ("patrol".into(), Box::new(Action::new("patrol".into(), |bb| log(bb, "patrol")))),
+            ("charge".into(), Box::new(Action::new("charge".into(), |bb| log(bb, "charge")))),
+            ("report".into(), Box::new(Action::new("report".into(), |bb| log(bb, "report")))),
+        ]);
+        let mut memory = BlackBoard::new();
+        BehaviorInbox::enqueue(&mut memory, "inbox", "patrol", 1);
+        BehaviorInbox::enqueue(&mut memory, "inbox", "charge", 5);
+        BehaviorInbox::enqueue(&mut memory, "inbox", "report", 1);
+        assert_eq!(memory.key_version("inbox"), 3);
+
+        for _ in 0..3 {
+            assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        }
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(executed(&memory), vec!["charge", "patrol", "report"]);
+        assert_eq!(memory.get::<BehaviorInbox>("inbox").unwrap().requests.len(), 0);
+    }
+
+    #[test]
+    fn test_running_request_finishes_first() {
+        let long = MockNode::new("long", vec![NodeResult::Running, NodeResult::Passed]);
+        let urgent = MockNode::new("urgent", vec![NodeResult::Passed]);
+        let (long_probe, urgent_probe) = (long.probe(), urgent.probe());
+        let mut node = InboxExecutor::new("executor".into(), "inbox".into(), vec![
+            ("long".into(), Box::new(long)),
+            ("urgent".into(), Box::new(urgent)),
+        ]);
+        let mut memory = BlackBoard::new();
+
+        BehaviorInbox::enqueue(&mut memory, "inbox", "long", 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        BehaviorInbox::enqueue(&mut memory, "inbox", "urgent", 9);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!((long_probe.ticks(), urgent_probe.ticks()), (2, 0));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(urgent_probe.ticks(), 1);
+    }
+
+    #[test]
+    fn test_unknown_request_reports_error() {
+        let mut node = InboxExecutor::new("executor".into(), "inbox".into(), vec![
+            ("patrol".into(), Box::new(Action::new("patrol".into(), |bb| log(bb, "patrol")))),
+        ]);
+        let mut memory = BlackBoard::new();
+        BehaviorInbox::enqueue(&mut memory, "inbox", "dance", 1);
+        BehaviorInbox::enqueue(&mut memory, "inbox", "patrol", 1);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.last_error().as_deref(), Some("unknown behavior 'dance'"));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.last_error(), None);
+    }
+}
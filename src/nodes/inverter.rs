@@ -1,32 +1,65 @@
-use crate::node::Node;
+use crate::node::{Node, NodeId, Trace, tick_child};
 use crate::blackboard::BlackBoard;
 use crate::node::NodeResult;
 
 pub struct Inverter {
     pub child: Box<dyn Node>,
+    pub id: NodeId,
     pub name: String
 }
 
 impl Inverter {
     pub fn new(child: Box<dyn Node>, name: String) -> Self {
-        Self { child, name }
+        Self { child, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        match tick_child(self.child.as_mut(), memory, &mut trace) {
+            NodeResult::Passed => NodeResult::Failed,
+            NodeResult::Failed => NodeResult::Passed,
+            NodeResult::Running => NodeResult::Running
+        }
     }
 }
 
 impl Node for Inverter {
     fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
-        match self.child.tick(memory) {
-            NodeResult::Passed => return NodeResult::Failed,
-            NodeResult::Failed => return NodeResult::Passed,
-            NodeResult::Running => return NodeResult::Running
-        }
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
     }
 
     fn get_name(&self) -> String {
         self.name.clone()
     }
 
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
     fn reset(&mut self) {
         self.child.reset();
     }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
 }
\ No newline at end of file
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// A selector that tries its children in order of when they last ran,
+/// oldest first, with children that never ran coming first in tree order.
+/// The first child that doesn't fail is stamped with the current time and
+/// its result returned; a running child keeps being ticked until it
+/// finishes, and if it fails the rest are tried in the same order. Over time
+/// every viable child gets its turn.
+pub struct LeastRecentlyRun {
+    pub children: Vec<Box<dyn Node>>,
+    pub last_run: Vec<Option<Instant>>,
+    pub current_child: Option<usize>,
+    pub clock: Arc<dyn Clock>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl LeastRecentlyRun {
+    pub fn new(name: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            last_run: vec![None; children.len()],
+            children,
+            current_child: None,
+            clock: Arc::new(SystemClock),
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let running = self.current_child.take();
+        let mut order: Vec<usize> = (0..self.children.len()).filter(|index| Some(*index) != running).collect();
+        order.sort_by_key(|index| self.last_run[*index]);
+        order.splice(0..0, running);
+
+        for index in order {
+            let result = tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error);
+            if result == NodeResult::Failed {
+                continue;
+            }
+            self.last_run[index] = Some(now);
+            if result == NodeResult::Running {
+                self.current_child = Some(index);
+            }
+            return result;
+        }
+        NodeResult::Failed
+    }
+}
+
+impl Node for LeastRecentlyRun {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::least_recently_run::LeastRecentlyRun;
+    use crate::tests::{MockNode, context_at};
+
+    fn mark(memory: &mut BlackBoard, chore: &'static str) -> NodeResult {
+        memory.data.insert("chore".into(), Box::new(chore));
+        NodeResult::Passed
+    }
+
+    fn chore(memory: &BlackBoard) -> &'static str {
+        memory.data.get("chore").and_then(|value| value.downcast_ref::<&str>()).copied().unwrap()
+    }
+
+    #[test]
+    fn test_least_recently_run_child_is_picked() {
+        let clock = ManualClock::new();
+        let mut node = LeastRecentlyRun::new("chores".into(), vec![
+            Box::new(Action::new("sweep".into(), |bb| mark(bb, "sweep"))),
+            Box::new(Action::new("cook".into(), |bb| mark(bb, "cook"))),
+            Box::new(Action::new("wash".into(), |bb| mark(bb, "wash"))),
+        ]).with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        let mut picked = Vec::new();
+        for _ in 0..6 {
+            clock.advance(Duration::from_secs(1));
+            assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+            picked.push(chore(&memory));
+        }
+        assert_eq!(picked, ["sweep", "cook", "wash", "sweep", "cook", "wash"]);
+    }
+
+    #[test]
+    fn test_failing_child_is_skipped_and_running_child_kept() {
+        let clock = ManualClock::new();
+        let broken = MockNode::new("broken", vec![NodeResult::Failed]);
+        let slow = MockNode::new("slow", vec![NodeResult::Running, NodeResult::Passed]);
+        let quick = MockNode::new("quick", vec![NodeResult::Passed]);
+        let (slow_probe, quick_probe) = (slow.probe(), quick.probe());
+        let mut node = LeastRecentlyRun::new("chores".into(), vec![Box::new(broken), Box::new(slow), Box::new(quick)])
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(quick_probe.ticks(), 0);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(quick_probe.ticks(), 1);
+        assert_eq!(slow_probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_failing_running_child_falls_through() {
+        let clock = ManualClock::new();
+        let flaky = MockNode::new("flaky", vec![NodeResult::Running, NodeResult::Failed]);
+        let backup = MockNode::new("backup", vec![NodeResult::Passed]);
+        let backup_probe = backup.probe();
+        let mut node = LeastRecentlyRun::new("chores".into(), vec![Box::new(flaky), Box::new(backup)])
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(backup_probe.ticks(), 1);
+        assert!(node.running_child().is_none());
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let mut node = LeastRecentlyRun::new("chores".into(), vec![
+            Box::new(Action::new("sweep".into(), |bb| mark(bb, "sweep"))),
+            Box::new(Action::new("cook".into(), |bb| mark(bb, "cook"))),
+        ]).with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+        let (first, second) = (context_at(&clock, 1), context_at(&clock, 3));
+
+        assert_eq!(node.tick_ctx(&mut memory, &first), NodeResult::Passed);
+        assert_eq!(node.tick_ctx(&mut memory, &second), NodeResult::Passed);
+        assert_eq!(node.last_run, [Some(first.now), Some(second.now)]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Lets its child run at most `limit` times over the lifetime of the node.
+/// Unlike the other decorators the execution count survives `reset`, so it
+/// bounds a behavior per mission rather than per episode. Once the limit is
+/// reached the child is no longer ticked and `exhausted_result` is returned.
+pub struct LimitExecutions {
+    pub child: Box<dyn Node>,
+    pub limit: usize,
+    pub executions: usize,
+    pub exhausted_result: NodeResult,
+    pub is_running: bool,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl LimitExecutions {
+    pub fn new(name: String, limit: usize, child: Box<dyn Node>) -> Self {
+        Self {
+            child,
+            limit,
+            executions: 0,
+            exhausted_result: NodeResult::Failed,
+            is_running: false,
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("LimitExecutions")?;
+        Ok(Self::new(config.name.clone(), config.count("limit")?, child)
+            .with_exhausted_result(config.result("exhausted_result")?))
+    }
+
+    pub fn with_exhausted_result(mut self, result: NodeResult) -> Self {
+        self.exhausted_result = result;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        if !self.is_running {
+            if self.executions >= self.limit {
+                return self.exhausted_result.clone();
+            }
+            self.executions += 1;
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        self.is_running = result == NodeResult::Running;
+        result
+    }
+}
+
+impl Node for LimitExecutions {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("LimitExecutions".into(), self.name.clone())
+            .with("limit", ConfigValue::Int(self.limit as i64))
+            .with("exhausted_result", ConfigValue::Result(self.exhausted_result.clone()))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.is_running = false;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::limit_executions::LimitExecutions;
+
+    fn count_tick(memory: &mut BlackBoard) -> NodeResult {
+        let count = memory.data.get("count")
+            .and_then(|value| value.downcast_ref::<usize>())
+            .copied()
+            .unwrap_or(0);
+        memory.data.insert("count".into(), Box::new(count + 1));
+        NodeResult::Passed
+    }
+
+    fn count(memory: &BlackBoard) -> usize {
+        *memory.data.get("count").unwrap().downcast_ref::<usize>().unwrap()
+    }
+
+    #[test]
+    fn test_limit_survives_reset() {
+        let mut node = LimitExecutions::new("limit".into(), 2, Box::new(Action::new("count".into(), count_tick)));
+        let mut memory = BlackBoard::new();
+
+        for _ in 0..3 {
+            node.tick(&mut memory);
+            node.tick(&mut memory);
+            node.reset();
+        }
+        assert_eq!(count(&memory), 2);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_running_child_counts_once() {
+        let mut node = LimitExecutions::new("limit".into(), 1, Box::new(Action::new("wait".into(), |_| NodeResult::Running)))
+            .with_exhausted_result(NodeResult::Passed);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.executions, 1);
+
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+}
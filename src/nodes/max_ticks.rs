@@ -0,0 +1,126 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Guards against runaway `Running` loops: once the child has been ticked
+/// `limit` times without finishing, the next tick resets it and fails
+/// instead. The count starts over whenever the child finishes.
+pub struct MaxTicks {
+    pub child: Box<dyn Node>,
+    pub limit: usize,
+    pub count: usize,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl MaxTicks {
+    pub fn new(name: String, limit: usize, child: Box<dyn Node>) -> Self {
+        Self { child, limit, count: 0, error: None, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("MaxTicks")?;
+        Ok(Self::new(config.name.clone(), config.count("limit")?, child))
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        self.count += 1;
+        if self.count > self.limit {
+            self.count = 0;
+            self.child.reset();
+            return NodeResult::Failed;
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        if result != NodeResult::Running {
+            self.count = 0;
+        }
+        result
+    }
+}
+
+impl Node for MaxTicks {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("MaxTicks".into(), self.name.clone())
+            .with("limit", ConfigValue::Int(self.limit as i64))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.count = 0;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::max_ticks::MaxTicks;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_fails_after_limit() {
+        let stuck = MockNode::new("stuck", vec![NodeResult::Running]);
+        let probe = stuck.probe();
+        let mut node = MaxTicks::new("guard".into(), 5, Box::new(stuck));
+        let mut memory = BlackBoard::new();
+
+        for _ in 0..5 {
+            assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        }
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 5);
+        assert_eq!(probe.resets(), 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_finishing_child_restarts_count() {
+        let child = MockNode::new("child", vec![NodeResult::Running, NodeResult::Passed]);
+        let mut node = MaxTicks::new("guard".into(), 2, Box::new(child));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.count, 0);
+    }
+}
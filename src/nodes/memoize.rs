@@ -0,0 +1,170 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Caches the child's last `Passed` or `Failed` together with a hash of the
+/// `T` values under `keys`. While those inputs hash the same the cached
+/// result is returned without ticking the child; any change, including a
+/// key appearing, disappearing or expiring, runs the child again. Only worth it for
+/// deterministic subtrees whose result depends on nothing but the inputs.
+pub struct Memoize<T> {
+    pub child: Box<dyn Node>,
+    pub keys: Vec<String>,
+    pub cached: Option<(u64, NodeResult)>,
+    pub id: NodeId,
+    pub name: String,
+    value_type: PhantomData<fn() -> T>
+}
+
+impl<T: Hash + 'static> Memoize<T> {
+    pub fn new(name: String, keys: Vec<String>, child: Box<dyn Node>) -> Self {
+        Self { child, keys, cached: None, id: NodeId::next(), name, value_type: PhantomData }
+    }
+
+    fn inputs(&self, memory: &BlackBoard) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for key in &self.keys {
+            memory.get::<T>(key).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let inputs = self.inputs(memory);
+        if let Some((hash, result)) = &self.cached {
+            if *hash == inputs {
+                return result.clone();
+            }
+            self.cached = None;
+            self.child.reset();
+        }
+
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        if result != NodeResult::Running {
+            self.cached = Some((inputs, result.clone()));
+        }
+        result
+    }
+}
+
+impl<T: Hash + 'static> Node for Memoize<T> {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.cached = None;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::memoize::Memoize;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_child_ticks_only_on_input_change() {
+        let child = MockNode::new("plan", vec![NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = Memoize::<i32>::new("cached".into(), vec!["x".into(), "y".into()], Box::new(child));
+        let mut memory = BlackBoard::new();
+        memory.data.insert("x".into(), Box::new(1));
+        memory.data.insert("y".into(), Box::new(2));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 1);
+
+        memory.data.insert("y".into(), Box::new(3));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+
+        memory.data.remove("x");
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 3);
+    }
+
+    #[test]
+    fn test_running_is_not_cached() {
+        let child = MockNode::new("plan", vec![NodeResult::Running, NodeResult::Failed, NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = Memoize::<i32>::new("cached".into(), vec!["x".into()], Box::new(child));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_reset_invalidates() {
+        let child = MockNode::new("plan", vec![NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = Memoize::<i32>::new("cached".into(), vec!["x".into()], Box::new(child));
+        let mut memory = BlackBoard::new();
+
+        node.tick(&mut memory);
+        node.reset();
+        node.tick(&mut memory);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_expired_input_counts_as_change() {
+        let clock = ManualClock::new();
+        let child = MockNode::new("plan", vec![NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = Memoize::<i32>::new("cached".into(), vec!["x".into()], Box::new(child));
+        let mut memory = BlackBoard::new().with_clock(Arc::new(clock.clone()));
+        memory.set_with_ttl("x", 1, Duration::from_secs(1));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+}
@@ -1,4 +1,59 @@
 pub mod action;
+pub mod condition;
 pub mod inverter;
+pub mod parallel;
+pub mod repeat;
+pub mod repeat_n;
+pub mod retry;
 pub mod selector;
-pub mod sequence;
\ No newline at end of file
+pub mod sequence;
+pub mod breadcrumb;
+pub mod limit_executions;
+pub mod hash_router;
+pub mod range_guard;
+pub mod write_status;
+pub mod timed_parallel;
+pub mod fallible_action;
+pub mod error_capture;
+pub mod caching_sequence;
+pub mod stream_status;
+pub mod fallthrough_selector;
+pub mod inbox;
+pub mod pid_action;
+pub mod abortable;
+pub mod order_check;
+pub mod hot_reload;
+pub mod pipeline;
+pub mod perturb;
+pub mod memoize;
+pub mod exclusive;
+pub mod condition_cache;
+pub mod frame_budget;
+pub mod budgeted_sequence;
+pub mod retry_on;
+pub mod vote_parallel;
+pub mod hysteresis_selector;
+pub mod least_recently_run;
+pub mod succeeder;
+pub mod failer;
+pub mod wait;
+pub mod cooldown;
+pub mod timeout;
+pub mod switch;
+pub mod reactive_sequence;
+pub mod reactive_selector;
+pub mod random_selector;
+pub mod weighted_random_selector;
+pub mod until;
+pub mod delay;
+pub mod max_ticks;
+pub mod guard;
+pub mod priority_selector;
+pub mod utility_selector;
+pub mod sub_tree;
+pub mod rate_limiter;
+pub mod yielder;
+pub mod once;
+pub mod repeat_while;
+pub mod cached_condition;
+pub mod stateful_action;
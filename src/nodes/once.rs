@@ -0,0 +1,128 @@
+use crate::blackboard::BlackBoard;
+use crate::config::NodeConfig;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Ticks its child until it first finishes and from then on returns that
+/// `Passed` or `Failed` without ticking the child again, e.g. for
+/// calibrating sensors once at startup. `reset` re-arms it.
+pub struct Once {
+    pub child: Box<dyn Node>,
+    pub cached: Option<NodeResult>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Once {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, cached: None, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("Once")?;
+        Ok(Self::new(config.name.clone(), child))
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.cached.is_some()
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        if let Some(result) = &self.cached {
+            return result.clone();
+        }
+
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        if result != NodeResult::Running {
+            self.cached = Some(result.clone());
+        }
+        result
+    }
+}
+
+impl Node for Once {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Once".into(), self.name.clone())
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.cached = None;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::once::Once;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_child_runs_until_first_completion() {
+        let calibrate = MockNode::new("calibrate", vec![NodeResult::Running, NodeResult::Passed, NodeResult::Failed]);
+        let probe = calibrate.probe();
+        let mut node = Once::new("calibrate_once".into(), Box::new(calibrate));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert!(!node.is_done());
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        for _ in 0..3 {
+            assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        }
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_failure_is_cached_until_reset() {
+        let calibrate = MockNode::new("calibrate", vec![NodeResult::Failed, NodeResult::Passed]);
+        let probe = calibrate.probe();
+        let mut node = Once::new("calibrate_once".into(), Box::new(calibrate));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 1);
+
+        node.reset();
+        assert!(!node.is_done());
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 2);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Debug decorator for custom composites. Once its child has returned
+/// `Passed` or `Failed`, the child must be reset before it is ticked again.
+/// A tick that breaks this rule is counted in `violations`, described by
+/// `last_error`, and answered with `Failed` without ticking the child.
+pub struct OrderCheck {
+    pub child: Box<dyn Node>,
+    pub finished: bool,
+    pub violations: usize,
+    pub violation: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl OrderCheck {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, finished: false, violations: 0, violation: None, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        if self.finished {
+            self.violations += 1;
+            self.violation = Some(format!("'{}' ticked again after finishing without a reset", self.child.get_name()));
+            return NodeResult::Failed;
+        }
+
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        self.finished = result != NodeResult::Running;
+        result
+    }
+}
+
+impl Node for OrderCheck {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.finished = false;
+        self.child.reset();
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.violation.clone().or_else(|| self.child.last_error())
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeId, NodeResult};
+    use crate::nodes::order_check::OrderCheck;
+    use crate::tests::MockNode;
+
+    // Ticks its child every tick, resetting it after it finishes only when
+    // `resets` is set.
+    struct Looper {
+        child: OrderCheck,
+        resets: bool,
+        id: NodeId
+    }
+
+    impl Node for Looper {
+        fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+            let result = self.child.tick(memory);
+            if self.resets && result != NodeResult::Running {
+                self.child.reset();
+            }
+            result
+        }
+
+        fn get_name(&self) -> String {
+            "looper".into()
+        }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
+
+        fn reset(&mut self) {
+            self.child.reset();
+        }
+    }
+
+    fn looper(resets: bool) -> Looper {
+        let child = MockNode::new("work", vec![NodeResult::Running, NodeResult::Passed]);
+        Looper { child: OrderCheck::new("check".into(), Box::new(child)), resets, id: NodeId::next() }
+    }
+
+    #[test]
+    fn test_correct_composite() {
+        let mut node = looper(true);
+        let mut memory = BlackBoard::new();
+        for _ in 0..6 {
+            node.tick(&mut memory);
+        }
+        assert_eq!(node.child.violations, 0);
+        assert_eq!(node.child.last_error(), None);
+    }
+
+    #[test]
+    fn test_buggy_composite() {
+        let mut node = looper(false);
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.child.violations, 1);
+        assert_eq!(node.child.last_error().as_deref(), Some("'work' ticked again after finishing without a reset"));
+    }
+}
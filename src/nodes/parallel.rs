@@ -0,0 +1,367 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// How many children of a `Parallel` have to pass for it to pass, or, as a
+/// failure policy, have to fail for it to fail.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParallelPolicy {
+    RequireAll,
+    RequireOne,
+    RequireCount(usize)
+}
+
+impl ParallelPolicy {
+    fn threshold(&self, children: usize) -> usize {
+        match self {
+            ParallelPolicy::RequireAll => children,
+            ParallelPolicy::RequireOne => 1,
+            ParallelPolicy::RequireCount(count) => *count
+        }
+    }
+
+    fn from_config(config: &NodeConfig, policy_key: &str, count_key: &str) -> Result<Self, String> {
+        Ok(match config.text(policy_key)?.as_str() {
+            "require_all" => ParallelPolicy::RequireAll,
+            "require_one" => ParallelPolicy::RequireOne,
+            "require_count" => ParallelPolicy::RequireCount(config.count(count_key)?),
+            other => return Err(format!("Parallel '{}' has unknown policy '{other}'", config.name))
+        })
+    }
+
+    fn to_config(&self, config: NodeConfig, policy_key: &str, count_key: &str) -> NodeConfig {
+        match self {
+            ParallelPolicy::RequireAll => config.with(policy_key, ConfigValue::Text("require_all".into())),
+            ParallelPolicy::RequireOne => config.with(policy_key, ConfigValue::Text("require_one".into())),
+            ParallelPolicy::RequireCount(count) => config
+                .with(policy_key, ConfigValue::Text("require_count".into()))
+                .with(count_key, ConfigValue::Int(*count as i64))
+        }
+    }
+}
+
+/// Ticks every child that is still running each tick, keeping each child's
+/// result across ticks, and then checks the policy. It passes once enough
+/// children have passed and fails as soon as too many have failed for the
+/// policy to still be met; a `RequireCount` above the number of children
+/// therefore fails on the first tick. Children still running when it
+/// finishes are halted through `interrupt`, so their `on_halt` cleanup runs,
+/// unless `halt_on_resolution` is turned off.
+///
+/// With an explicit `failure_policy` it instead fails once that many
+/// children have failed, e.g. only when all of them did. Success takes
+/// precedence when both thresholds are met in the same tick, and it fails
+/// when every child has finished without meeting either threshold.
+pub struct Parallel {
+    pub children: Vec<Box<dyn Node>>,
+    pub states: Vec<NodeResult>,
+    pub policy: ParallelPolicy,
+    pub failure_policy: Option<ParallelPolicy>,
+    pub halt_on_resolution: bool,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Parallel {
+    pub fn new(name: String, policy: ParallelPolicy, children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            states: vec![NodeResult::Running; children.len()],
+            children,
+            policy,
+            failure_policy: None,
+            halt_on_resolution: true,
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    /// With `false`, children still running when the parallel finishes are
+    /// left as they are and carry on from there the next time it runs.
+    pub fn with_halt_on_resolution(mut self, halt: bool) -> Self {
+        self.halt_on_resolution = halt;
+        self
+    }
+
+    pub fn with_policies(name: String, policy: ParallelPolicy, failure_policy: ParallelPolicy, children: Vec<Box<dyn Node>>) -> Self {
+        Self { failure_policy: Some(failure_policy), ..Self::new(name, policy, children) }
+    }
+
+    pub fn from_config(config: &NodeConfig, children: Vec<Box<dyn Node>>) -> Result<Self, String> {
+        config.expect_kind("Parallel")?;
+        let policy = ParallelPolicy::from_config(config, "policy", "count")?;
+        let failure_policy = match config.params.contains_key("failure_policy") {
+            true => Some(ParallelPolicy::from_config(config, "failure_policy", "failure_count")?),
+            false => None
+        };
+        let halt_on_resolution = match config.params.contains_key("halt_on_resolution") {
+            true => config.bool("halt_on_resolution")?,
+            false => true
+        };
+        Ok(Self { failure_policy, halt_on_resolution, ..Self::new(config.name.clone(), policy, children) })
+    }
+
+    fn finish(&mut self, result: NodeResult) -> NodeResult {
+        if result != NodeResult::Failed {
+            self.error = None;
+        }
+        for (child, state) in self.children.iter_mut().zip(&mut self.states) {
+            if *state == NodeResult::Running && self.halt_on_resolution {
+                child.interrupt();
+            }
+            *state = NodeResult::Running;
+        }
+        result
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        for (child, state) in self.children.iter_mut().zip(&mut self.states) {
+            if *state == NodeResult::Running {
+                *state = tick_child_with_error(child.as_mut(), memory, &mut trace, &mut self.error);
+            }
+        }
+
+        let required = self.policy.threshold(self.children.len());
+        let passed = self.states.iter().filter(|state| **state == NodeResult::Passed).count();
+        let failed = self.states.iter().filter(|state| **state == NodeResult::Failed).count();
+        if passed >= required {
+            return self.finish(NodeResult::Passed);
+        }
+        let gave_up = match &self.failure_policy {
+            Some(failure_policy) => failed >= failure_policy.threshold(self.children.len()) || passed + failed == self.children.len(),
+            None => self.children.len() - failed < required
+        };
+        if gave_up {
+            return self.finish(NodeResult::Failed);
+        }
+        NodeResult::Running
+    }
+}
+
+impl Node for Parallel {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        let mut config = self.policy.to_config(NodeConfig::new("Parallel".into(), self.name.clone()), "policy", "count");
+        if !self.halt_on_resolution {
+            config = config.with("halt_on_resolution", ConfigValue::Bool(false));
+        }
+        match &self.failure_policy {
+            Some(failure_policy) => failure_policy.to_config(config, "failure_policy", "failure_count"),
+            None => config
+        }
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        for (child, state) in self.children.iter_mut().zip(&mut self.states) {
+            child.reset();
+            *state = NodeResult::Running;
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::fallible_action::FallibleAction;
+    use crate::nodes::parallel::{Parallel, ParallelPolicy};
+    use crate::tests::MockNode;
+
+    fn children() -> Vec<Box<dyn Node>> {
+        vec![
+            Box::new(MockNode::new("quick", vec![NodeResult::Passed])),
+            Box::new(MockNode::new("slow", vec![NodeResult::Running, NodeResult::Passed])),
+            Box::new(MockNode::new("broken", vec![NodeResult::Running, NodeResult::Failed])),
+        ]
+    }
+
+    fn run(policy: ParallelPolicy) -> Vec<NodeResult> {
+        let mut node = Parallel::new("team".into(), policy, children());
+        let mut memory = BlackBoard::new();
+        vec![node.tick(&mut memory), node.tick(&mut memory)]
+    }
+
+    #[test]
+    fn test_require_all() {
+        assert_eq!(run(ParallelPolicy::RequireAll), [NodeResult::Running, NodeResult::Failed]);
+    }
+
+    #[test]
+    fn test_require_one() {
+        assert_eq!(run(ParallelPolicy::RequireOne), [NodeResult::Passed, NodeResult::Passed]);
+    }
+
+    #[test]
+    fn test_require_count() {
+        assert_eq!(run(ParallelPolicy::RequireCount(2)), [NodeResult::Running, NodeResult::Passed]);
+    }
+
+    #[test]
+    fn test_require_count_equal_to_children() {
+        let mut node = Parallel::new("team".into(), ParallelPolicy::RequireCount(2), vec![
+            Box::new(MockNode::new("a", vec![NodeResult::Passed])),
+            Box::new(MockNode::new("b", vec![NodeResult::Running, NodeResult::Passed])),
+        ]);
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_require_count_above_children_fails() {
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let probe = slow.probe();
+        let mut node = Parallel::new("team".into(), ParallelPolicy::RequireCount(3), vec![
+            Box::new(MockNode::new("quick", vec![NodeResult::Passed])),
+            Box::new(slow),
+        ]);
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+        assert_eq!(probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_reset_restores_states() {
+        let mut node = Parallel::new("team".into(), ParallelPolicy::RequireAll, children());
+        node.tick(&mut BlackBoard::new());
+        assert_eq!(node.states, [NodeResult::Passed, NodeResult::Running, NodeResult::Running]);
+
+        node.reset();
+        assert!(node.states.iter().all(|state| *state == NodeResult::Running));
+    }
+
+    #[test]
+    fn test_fail_only_when_all_fail() {
+        let mut node = Parallel::with_policies("search".into(), ParallelPolicy::RequireOne, ParallelPolicy::RequireAll, vec![
+            Box::new(MockNode::new("left", vec![NodeResult::Failed])),
+            Box::new(MockNode::new("right", vec![NodeResult::Running, NodeResult::Failed])),
+        ]);
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_conflicting_thresholds() {
+        let mut node = Parallel::with_policies("team".into(), ParallelPolicy::RequireCount(2), ParallelPolicy::RequireOne, children());
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+
+        let mut node = Parallel::with_policies("team".into(), ParallelPolicy::RequireAll, ParallelPolicy::RequireOne, children());
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_success_takes_precedence() {
+        let mut node = Parallel::with_policies("race".into(), ParallelPolicy::RequireOne, ParallelPolicy::RequireOne, vec![
+            Box::new(MockNode::new("fails", vec![NodeResult::Failed])),
+            Box::new(MockNode::new("passes", vec![NodeResult::Passed])),
+        ]);
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_no_threshold_met_fails_when_done() {
+        let mut node = Parallel::with_policies("team".into(), ParallelPolicy::RequireAll, ParallelPolicy::RequireAll, children());
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_failure_policy_config_round_trip() {
+        let node = Parallel::with_policies("team".into(), ParallelPolicy::RequireOne, ParallelPolicy::RequireCount(2), children());
+        let rebuilt = Parallel::from_config(&node.to_config(), children()).unwrap();
+        assert_eq!(rebuilt.failure_policy, Some(ParallelPolicy::RequireCount(2)));
+        assert_eq!(Parallel::from_config(&Parallel::new("team".into(), ParallelPolicy::RequireAll, children()).to_config(), children()).unwrap().failure_policy, None);
+    }
+
+    #[test]
+    fn test_resolution_halts_running_sibling() {
+        let halts = Arc::new(AtomicUsize::new(0));
+        let counter = halts.clone();
+        let grasp = Action::with_halt("grasp".into(), |_| NodeResult::Running, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let scan = MockNode::new("scan", vec![NodeResult::Running, NodeResult::Passed]);
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let probe = slow.probe();
+        let mut node = Parallel::new("team".into(), ParallelPolicy::RequireOne, vec![Box::new(scan), Box::new(slow), Box::new(grasp)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.resets(), 1);
+        assert_eq!(halts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_resolution_without_halting() {
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let probe = slow.probe();
+        let mut node = Parallel::new("team".into(), ParallelPolicy::RequireOne, vec![
+            Box::new(MockNode::new("quick", vec![NodeResult::Passed])),
+            Box::new(slow),
+        ]).with_halt_on_resolution(false);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+        assert_eq!(probe.resets(), 0);
+
+        let config = node.to_config();
+        assert!(!Parallel::from_config(&config, vec![]).unwrap().halt_on_resolution);
+    }
+
+    #[test]
+    fn test_failed_child_error_is_kept() {
+        fn jammed(_: &mut BlackBoard) -> Result<NodeResult, String> {
+            Err("door jammed".into())
+        }
+        let mut node = Parallel::new("team".into(), ParallelPolicy::RequireAll, vec![
+            Box::new(MockNode::new("walk", vec![NodeResult::Running])),
+            Box::new(FallibleAction::new("open".into(), jammed)),
+        ]);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+        assert_eq!(node.last_error().as_deref(), Some("door jammed"));
+        node.reset();
+        assert_eq!(node.last_error(), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult};
+use crate::rng::Rng;
+
+/// Adds uniform noise in `[-magnitude, magnitude]` to the `f64` under `key`
+/// every tick and clamps the result to `[min, max]`, to add variety to
+/// otherwise deterministic behaviour. Always passes; a missing or non-`f64`
+/// value is left alone. Use `with_seed` for reproducible runs. Bounds that
+/// are NaN or with `min` above `max` are rejected by `new`.
+pub struct Perturb {
+    pub key: String,
+    pub magnitude: f64,
+    pub min: f64,
+    pub max: f64,
+    pub rng: Rng,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Perturb {
+    pub fn new(name: String, key: String, magnitude: f64, min: f64, max: f64) -> Result<Self, String> {
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(format!("Perturb '{name}' needs min <= max, got min {min} and max {max}"));
+        }
+        Ok(Self { key, magnitude, min, max, rng: Rng::from_entropy(), id: NodeId::next(), name })
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+}
+
+impl Node for Perturb {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        memory.update::<f64>(&self.key, |value| {
+            let noise = self.rng.range_f64(-self.magnitude, self.magnitude);
+            *value = (*value + noise).clamp(self.min, self.max);
+        });
+        NodeResult::Passed
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) { }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::perturb::Perturb;
+
+    fn walk(seed: u64, ticks: usize) -> Vec<f64> {
+        let mut node = Perturb::new("explore".into(), "speed".into(), 0.5, 0.0, 2.0).unwrap().with_seed(seed);
+        let mut memory = BlackBoard::new();
+        memory.data.insert("speed".into(), Box::new(1.0_f64));
+        (0..ticks).map(|_| {
+            assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+            *memory.data.get("speed").unwrap().downcast_ref::<f64>().unwrap()
+        }).collect()
+    }
+
+    #[test]
+    fn test_seeded_noise_is_reproducible() {
+        let first = walk(7, 50);
+        assert_eq!(first, walk(7, 50));
+        assert_ne!(first, walk(8, 50));
+    }
+
+    #[test]
+    fn test_noise_stays_in_bounds() {
+        let values = walk(42, 500);
+        assert!(values.iter().all(|value| (0.0..=2.0).contains(value)));
+
+        let mut previous = 1.0;
+        for value in values {
+            assert!((value - previous).abs() <= 0.5);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_missing_key_passes() {
+        let mut node = Perturb::new("explore".into(), "speed".into(), 0.5, 0.0, 2.0).unwrap().with_seed(1);
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(!memory.contains_key("speed"));
+    }
+
+    #[test]
+    fn test_change_is_observed_and_expiry_respected() {
+        let clock = ManualClock::new();
+        let mut node = Perturb::new("explore".into(), "speed".into(), 0.5, 0.0, 2.0).unwrap().with_seed(1);
+        let mut memory = BlackBoard::new().with_clock(Arc::new(clock.clone()));
+        memory.set_with_ttl("speed", 1.0_f64, Duration::from_secs(1));
+        let version = memory.key_version("speed");
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(memory.key_version("speed") > version);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(!memory.data.contains_key("speed"));
+    }
+
+    #[test]
+    fn test_invalid_bounds_rejected() {
+        let error = Perturb::new("explore".into(), "speed".into(), 0.5, 2.0, 0.0).err().unwrap();
+        assert_eq!(error, "Perturb 'explore' needs min <= max, got min 2 and max 0");
+        assert!(Perturb::new("explore".into(), "speed".into(), 0.5, f64::NAN, 2.0).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::node::{Node, NodeId, NodeResult};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PidGains {
+    pub kp: f64,
+    pub ki: f64,
+    pub kd: f64
+}
+
+/// A PID controller leaf. Each tick reads the `f64` setpoint and measurement
+/// keys, writes the control signal to the output key and returns `Running`
+/// until the error is within `tolerance`, then `Passed`. The time step is
+/// measured between ticks, in tick context time if there is one, else by
+/// the node's clock; the first tick after a reset is proportional only.
+/// Fails when either input is missing.
+pub struct PidAction {
+    pub gains: PidGains,
+    pub setpoint_key: String,
+    pub measurement_key: String,
+    pub output_key: String,
+    pub tolerance: f64,
+    pub integral: f64,
+    pub last: Option<(Instant, f64)>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl PidAction {
+    pub fn new(name: String, gains: PidGains, setpoint_key: String, measurement_key: String, output_key: String, tolerance: f64) -> Self {
+        Self {
+            gains,
+            setpoint_key,
+            measurement_key,
+            output_key,
+            tolerance,
+            integral: 0.0,
+            last: None,
+            suspended_at: None,
+            clock: Arc::new(SystemClock),
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn read(memory: &BlackBoard, key: &str) -> Option<f64> {
+        memory.get_cloned::<f64>(key)
+    }
+}
+
+impl Node for PidAction {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        let (Some(setpoint), Some(measurement)) = (
+            Self::read(memory, &self.setpoint_key),
+            Self::read(memory, &self.measurement_key)
+        ) else {
+            return NodeResult::Failed;
+        };
+
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let error = setpoint - measurement;
+        let mut output = self.gains.kp * error;
+        if let Some((last_time, last_error)) = self.last {
+            let dt = now.duration_since(last_time).as_secs_f64();
+            if dt > 0.0 {
+                self.integral += error * dt;
+                output += self.gains.ki * self.integral + self.gains.kd * (error - last_error) / dt;
+            }
+        }
+        self.last = Some((now, error));
+
+        if error.abs() <= self.tolerance {
+            memory.set(&self.output_key, 0.0_f64);
+            self.integral = 0.0;
+            self.last = None;
+            return NodeResult::Passed;
+        }
+        memory.set(&self.output_key, output);
+        NodeResult::Running
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last = None;
+        self.suspended_at = None;
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some((last_time, _)), Some(suspended_at)) = (&mut self.last, self.suspended_at.take()) {
+            *last_time += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::pid_action::{PidAction, PidGains};
+    use crate::tests::context_at;
+
+    fn value(memory: &BlackBoard, key: &str) -> f64 {
+        *memory.data.get(key).unwrap().downcast_ref::<f64>().unwrap()
+    }
+
+    #[test]
+    fn test_first_order_plant_converges() {
+        let clock = ManualClock::new();
+        let gains = PidGains { kp: 1.2, ki: 0.3, kd: 0.05 };
+        let mut node = PidAction::new("hold_speed".into(), gains, "target".into(), "speed".into(), "throttle".into(), 0.01)
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+        memory.data.insert("target".into(), Box::new(5.0f64));
+        memory.data.insert("speed".into(), Box::new(0.0f64));
+
+        let dt = 0.1;
+        let mut ticks = 0;
+        let result = loop {
+            let result = node.tick(&mut memory);
+            ticks += 1;
+            if result != NodeResult::Running || ticks > 1000 {
+                break result;
+            }
+            // First-order plant: the speed relaxes towards the throttle input.
+            let speed = value(&memory, "speed");
+            let throttle = value(&memory, "throttle");
+            memory.data.insert("speed".into(), Box::new(speed + (throttle * 2.0 - speed * 0.5) * dt));
+            clock.advance(Duration::from_secs_f64(dt));
+        };
+
+        assert_eq!(result, NodeResult::Passed);
+        assert!(ticks > 1);
+        assert!((value(&memory, "speed") - 5.0).abs() <= 0.01);
+    }
+
+    #[test]
+    fn test_missing_input_fails() {
+        let gains = PidGains { kp: 1.0, ki: 0.0, kd: 0.0 };
+        let mut node = PidAction::new("pid".into(), gains, "target".into(), "speed".into(), "throttle".into(), 0.01);
+        let mut memory = BlackBoard::new();
+        memory.data.insert("target".into(), Box::new(1.0f64));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let gains = PidGains { kp: 0.0, ki: 1.0, kd: 0.0 };
+        let mut node = PidAction::new("pid".into(), gains, "target".into(), "speed".into(), "throttle".into(), 0.01)
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+        memory.set("target", 1.0_f64);
+        memory.set("speed", 0.0_f64);
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(value(&memory, "throttle"), 0.0);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Running);
+        assert_eq!(value(&memory, "throttle"), 2.0);
+        assert_eq!(memory.key_version("throttle"), memory.version());
+    }
+}
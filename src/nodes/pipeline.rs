@@ -0,0 +1,188 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Threads one blackboard value through its stages in order. Every stage
+/// reads `input_key` and writes its result to `output_key`; once a stage
+/// passes, the value under `output_key` is moved to `input_key` for the next
+/// stage. After the last stage the final value is left under `output_key`.
+///
+/// A stage that passes without writing `output_key` fails the pipeline. A
+/// `Running` stage is resumed on the next tick instead of starting over,
+/// since the earlier stages have already consumed their input.
+pub struct Pipeline {
+    pub stages: Vec<Box<dyn Node>>,
+    pub input_key: String,
+    pub output_key: String,
+    pub current_stage: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Pipeline {
+    pub fn new(name: String, input_key: String, output_key: String, stages: Vec<Box<dyn Node>>) -> Self {
+        Self { stages, input_key, output_key, current_stage: None, error: None, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, stages: Vec<Box<dyn Node>>) -> Result<Self, String> {
+        config.expect_kind("Pipeline")?;
+        Ok(Self::new(config.name.clone(), config.text("input_key")?, config.text("output_key")?, stages))
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let start = match self.current_stage.take() {
+            Some(index) => index,
+            None => {
+                memory.remove(&self.output_key);
+                0
+            }
+        };
+        for index in start..self.stages.len() {
+            if index > start {
+                let Some((value, expiry)) = memory.take_entry(&self.output_key) else {
+                    return NodeResult::Failed;
+                };
+                memory.notify(&self.output_key);
+                memory.insert_entry(&self.input_key, value, expiry);
+            }
+            match tick_child_with_error(self.stages[index].as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Passed => {
+                    if !memory.contains_key(&self.output_key) {
+                        return NodeResult::Failed;
+                    }
+                },
+                NodeResult::Running => {
+                    self.current_stage = Some(index);
+                    return NodeResult::Running;
+                },
+                NodeResult::Failed => return NodeResult::Failed
+            }
+        }
+        NodeResult::Passed
+    }
+}
+
+impl Node for Pipeline {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Pipeline".into(), self.name.clone())
+            .with("input_key", ConfigValue::Text(self.input_key.clone()))
+            .with("output_key", ConfigValue::Text(self.output_key.clone()))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_stage = None;
+
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_stage.map(|index| self.stages[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.stages.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.stages.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::pipeline::Pipeline;
+
+    fn stage(memory: &mut BlackBoard, transform: fn(i64) -> i64) -> NodeResult {
+        let Some(value) = memory.data.get("in").and_then(|value| value.downcast_ref::<i64>()).copied() else {
+            return NodeResult::Failed;
+        };
+        memory.data.insert("out".into(), Box::new(transform(value)));
+        NodeResult::Passed
+    }
+
+    fn output(memory: &BlackBoard) -> Option<i64> {
+        memory.data.get("out").and_then(|value| value.downcast_ref::<i64>()).copied()
+    }
+
+    #[test]
+    fn test_three_stage_pipeline() {
+        let mut node = Pipeline::new("transform".into(), "in".into(), "out".into(), vec![
+            Box::new(Action::new("double".into(), |bb| stage(bb, |value| value * 2))),
+            Box::new(Action::new("increment".into(), |bb| stage(bb, |value| value + 1))),
+            Box::new(Action::new("square".into(), |bb| stage(bb, |value| value * value))),
+        ]);
+        let mut memory = BlackBoard::new();
+        memory.data.insert("in".into(), Box::new(3_i64));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(output(&memory), Some(49));
+        assert!(memory.key_version("in") > 0);
+    }
+
+    #[test]
+    fn test_running_stage_resumes() {
+        let mut node = Pipeline::new("transform".into(), "in".into(), "out".into(), vec![
+            Box::new(Action::new("double".into(), |bb| stage(bb, |value| value * 2))),
+            Box::new(Action::new("wait".into(), |bb| {
+                let ready = bb.data.get("ready").and_then(|value| value.downcast_ref::<bool>()).copied().unwrap_or(false);
+                if !ready {
+                    return NodeResult::Running;
+                }
+                stage(bb, |value| value + 1)
+            })),
+        ]);
+        let mut memory = BlackBoard::new();
+        memory.data.insert("in".into(), Box::new(5_i64));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "wait");
+        memory.data.insert("ready".into(), Box::new(true));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(output(&memory), Some(11));
+    }
+
+    #[test]
+    fn test_stage_without_output_fails() {
+        let mut node = Pipeline::new("transform".into(), "in".into(), "out".into(), vec![
+            Box::new(Action::new("noop".into(), |_| NodeResult::Passed)),
+            Box::new(Action::new("double".into(), |bb| stage(bb, |value| value * 2))),
+        ]);
+        let mut memory = BlackBoard::new();
+        memory.data.insert("in".into(), Box::new(1_i64));
+        memory.data.insert("out".into(), Box::new(100_i64));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+}
@@ -0,0 +1,152 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+pub type PriorityFn = Box<dyn Fn(&BlackBoard) -> i32 + Send + Sync>;
+
+/// A selector whose order depends on the world: every tick it evaluates each
+/// child's priority, then tries the children from the highest priority down
+/// until one doesn't fail. Ties keep their registration order. When a
+/// different child ends up running, or the selector finishes, the child
+/// that was running before is reset.
+pub struct PrioritySelector {
+    pub children: Vec<(PriorityFn, Box<dyn Node>)>,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl PrioritySelector {
+    pub fn new(name: String, children: Vec<(PriorityFn, Box<dyn Node>)>) -> Self {
+        Self { children, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    fn finish(&mut self, running: Option<usize>, result: NodeResult) -> NodeResult {
+        if let Some(previous) = self.current_child
+            && running != Some(previous) {
+            self.children[previous].1.reset();
+        }
+        self.current_child = running;
+        result
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let mut order: Vec<(i32, usize)> = self.children.iter().enumerate()
+            .map(|(index, (priority, _))| (priority(memory), index))
+            .collect();
+        order.sort_by_key(|(priority, _)| std::cmp::Reverse(*priority));
+
+        for (_, index) in order {
+            match tick_child_with_error(self.children[index].1.as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Failed => continue,
+                NodeResult::Running => return self.finish(Some(index), NodeResult::Running),
+                NodeResult::Passed => return self.finish(None, NodeResult::Passed)
+            }
+        }
+        self.finish(None, NodeResult::Failed)
+    }
+}
+
+impl Node for PrioritySelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for (_, child) in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].1.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|(_, child)| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|(_, child)| child.as_mut() as &mut dyn Node).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::priority_selector::{PriorityFn, PrioritySelector};
+    use crate::tests::MockNode;
+
+    fn health(memory: &BlackBoard) -> i32 {
+        memory.get::<i32>("health").copied().unwrap_or(100)
+    }
+
+    fn mark(memory: &mut BlackBoard, branch: &'static str) -> NodeResult {
+        memory.set("branch", branch);
+        NodeResult::Passed
+    }
+
+    #[test]
+    fn test_priorities_reorder_branches() {
+        let flee: PriorityFn = Box::new(|bb| 100 - health(bb));
+        let fight: PriorityFn = Box::new(|_| 50);
+        let mut node = PrioritySelector::new("combat".into(), vec![
+            (flee, Box::new(Action::new("flee".into(), |bb| mark(bb, "flee")))),
+            (fight, Box::new(Action::new("fight".into(), |bb| mark(bb, "fight")))),
+        ]);
+        let mut memory = BlackBoard::new();
+
+        memory.set("health", 90_i32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"fight"));
+
+        memory.set("health", 20_i32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"flee"));
+    }
+
+    #[test]
+    fn test_reprioritised_branch_resets_running_one() {
+        let patrol = MockNode::new("patrol", vec![NodeResult::Running]);
+        let probe = patrol.probe();
+        let mut node = PrioritySelector::new("behaviour".into(), vec![
+            (Box::new(|bb: &BlackBoard| 100 - health(bb)), Box::new(Action::new("flee".into(), |_| NodeResult::Running))),
+            (Box::new(|_: &BlackBoard| 50), Box::new(patrol)),
+        ]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "patrol");
+
+        memory.set("health", 10_i32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "flee");
+        assert_eq!(probe.resets(), 1);
+    }
+}
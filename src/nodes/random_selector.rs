@@ -0,0 +1,159 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+use crate::rng::Rng;
+
+/// A selector that tries its children in a freshly shuffled order on every
+/// new evaluation, so NPCs don't always fall back to the same option. A
+/// running child is resumed on the next tick in the same order.
+pub struct RandomSelector {
+    pub children: Vec<Box<dyn Node>>,
+    pub order: Vec<usize>,
+    pub current_child: Option<usize>,
+    pub rng: Rng,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl RandomSelector {
+    pub fn new(name: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self::with_rng(name, children, Rng::from_entropy())
+    }
+
+    pub fn with_seed(name: String, children: Vec<Box<dyn Node>>, seed: u64) -> Self {
+        Self::with_rng(name, children, Rng::new(seed))
+    }
+
+    fn with_rng(name: String, children: Vec<Box<dyn Node>>, rng: Rng) -> Self {
+        Self { children, order: Vec::new(), current_child: None, rng, error: None, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let start = match self.current_child.take() {
+            Some(position) => position,
+            None => {
+                self.order = (0..self.children.len()).collect();
+                self.rng.shuffle(&mut self.order);
+                0
+            }
+        };
+        for position in start..self.order.len() {
+            let child = &mut self.children[self.order[position]];
+            match tick_child_with_error(child.as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Failed => continue,
+                NodeResult::Running => {
+                    self.current_child = Some(position);
+                    return NodeResult::Running;
+                },
+                NodeResult::Passed => {
+                    child.reset();
+                    return NodeResult::Passed;
+                }
+            }
+        }
+        NodeResult::Failed
+    }
+}
+
+impl Node for RandomSelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|position| self.children[self.order[position]].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::random_selector::RandomSelector;
+
+    fn record(memory: &mut BlackBoard, index: usize, result: NodeResult) -> NodeResult {
+        if !memory.contains_key("tried") {
+            memory.set("tried", Vec::<usize>::new());
+        }
+        memory.get_mut::<Vec<usize>>("tried").unwrap().push(index);
+        result
+    }
+
+    fn selector(seed: u64) -> RandomSelector {
+        RandomSelector::with_seed("options".into(), vec![
+            Box::new(Action::new("a".into(), |bb| record(bb, 0, NodeResult::Failed))),
+            Box::new(Action::new("b".into(), |bb| record(bb, 1, NodeResult::Failed))),
+            Box::new(Action::new("c".into(), |bb| record(bb, 2, NodeResult::Failed))),
+            Box::new(Action::new("d".into(), |bb| record(bb, 3, NodeResult::Failed))),
+        ], seed)
+    }
+
+    fn tried(node: &mut RandomSelector) -> Vec<usize> {
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        memory.get::<Vec<usize>>("tried").unwrap().clone()
+    }
+
+    #[test]
+    fn test_seeded_order_is_reproducible() {
+        let (mut first, mut second) = (selector(7), selector(7));
+        let orders: Vec<Vec<usize>> = (0..5).map(|_| tried(&mut first)).collect();
+        for order in &orders {
+            assert_eq!(&tried(&mut second), order);
+            let mut sorted = order.clone();
+            sorted.sort();
+            assert_eq!(sorted, vec![0, 1, 2, 3]);
+        }
+        assert!(orders.iter().any(|order| order != &orders[0]));
+    }
+
+    #[test]
+    fn test_passes_on_first_passing_child() {
+        let mut node = RandomSelector::with_seed("options".into(), vec![
+            Box::new(Action::new("a".into(), |bb| record(bb, 0, NodeResult::Failed))),
+            Box::new(Action::new("b".into(), |bb| record(bb, 1, NodeResult::Passed))),
+        ], 3);
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<Vec<usize>>("tried").unwrap().last(), Some(&1));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult};
+
+/// Checks that the `f64` stored under `key` lies within `[min, max]`.
+/// With `clamp` enabled an out-of-range value is written back clamped to the
+/// range and the guard passes; otherwise it fails. A missing key always fails.
+/// Bounds that are NaN or with `min` above `max` are rejected by `new`.
+pub struct RangeGuard {
+    pub key: String,
+    pub min: f64,
+    pub max: f64,
+    pub clamp: bool,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl RangeGuard {
+    pub fn new(name: String, key: String, min: f64, max: f64) -> Result<Self, String> {
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(format!("RangeGuard '{name}' needs min <= max, got min {min} and max {max}"));
+        }
+        Ok(Self { key, min, max, clamp: false, id: NodeId::next(), name })
+    }
+
+    pub fn from_config(config: &NodeConfig) -> Result<Self, String> {
+        config.expect_kind("RangeGuard")?;
+        let guard = Self::new(config.name.clone(), config.text("key")?, config.float("min")?, config.float("max")?)?;
+        Ok(Self { clamp: config.bool("clamp")?, ..guard })
+    }
+
+    pub fn with_clamp(mut self) -> Self {
+        self.clamp = true;
+        self
+    }
+}
+
+impl Node for RangeGuard {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        let Some(value) = memory.get_cloned::<f64>(&self.key) else {
+            return NodeResult::Failed;
+        };
+
+        if value >= self.min && value <= self.max {
+            return NodeResult::Passed;
+        }
+        if self.clamp && !value.is_nan() {
+            let (min, max) = (self.min, self.max);
+            memory.update::<f64>(&self.key, |value| *value = value.clamp(min, max));
+            return NodeResult::Passed;
+        }
+        NodeResult::Failed
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("RangeGuard".into(), self.name.clone())
+            .with("key", ConfigValue::Text(self.key.clone()))
+            .with("min", ConfigValue::Float(self.min))
+            .with("max", ConfigValue::Float(self.max))
+            .with("clamp", ConfigValue::Bool(self.clamp))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) { }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::range_guard::RangeGuard;
+
+    fn memory_with(value: f64) -> BlackBoard {
+        let mut memory = BlackBoard::new();
+        memory.data.insert("speed".into(), Box::new(value));
+        memory
+    }
+
+    fn speed(memory: &BlackBoard) -> f64 {
+        *memory.data.get("speed").unwrap().downcast_ref::<f64>().unwrap()
+    }
+
+    #[test]
+    fn test_in_range_passes() {
+        let mut node = RangeGuard::new("speed_ok".into(), "speed".into(), 0.0, 2.0).unwrap();
+        let mut memory = memory_with(1.5);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(speed(&memory), 1.5);
+    }
+
+    #[test]
+    fn test_out_of_range_fails() {
+        let mut node = RangeGuard::new("speed_ok".into(), "speed".into(), 0.0, 2.0).unwrap();
+        let mut memory = memory_with(3.0);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(speed(&memory), 3.0);
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_out_of_range_clamped() {
+        let mut node = RangeGuard::new("speed_ok".into(), "speed".into(), 0.0, 2.0).unwrap().with_clamp();
+        let mut memory = memory_with(-4.0);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(speed(&memory), 0.0);
+        assert!(memory.key_version("speed") > 0);
+    }
+
+    #[test]
+    fn test_invalid_bounds_rejected() {
+        let error = RangeGuard::new("speed_ok".into(), "speed".into(), 2.0, 0.0).err().unwrap();
+        assert_eq!(error, "RangeGuard 'speed_ok' needs min <= max, got min 2 and max 0");
+        assert!(RangeGuard::new("speed_ok".into(), "speed".into(), 0.0, f64::NAN).is_err());
+    }
+}
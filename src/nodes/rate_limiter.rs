@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Ticks its child at most once per `min_interval`, whatever it returns.
+/// Ticks arriving sooner get the child's last result without ticking it,
+/// e.g. for expensive perception that need not run every frame. Unlike
+/// `Cooldown` this throttles every tick, not only after a success.
+pub struct RateLimiter {
+    pub child: Box<dyn Node>,
+    pub min_interval: Duration,
+    pub last_tick: Option<Instant>,
+    pub last_result: Option<NodeResult>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl RateLimiter {
+    pub fn new(name: String, min_interval: Duration, child: Box<dyn Node>) -> Self {
+        Self {
+            child,
+            min_interval,
+            last_tick: None,
+            last_result: None,
+            suspended_at: None,
+            clock: Arc::new(SystemClock),
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("RateLimiter")?;
+        Ok(Self::new(config.name.clone(), config.duration("min_interval")?, child))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        if let (Some(last), Some(result)) = (self.last_tick, &self.last_result)
+            && now.duration_since(last) < self.min_interval {
+            return result.clone();
+        }
+
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        self.last_tick = Some(now);
+        self.last_result = Some(result.clone());
+        result
+    }
+}
+
+impl Node for RateLimiter {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("RateLimiter".into(), self.name.clone())
+            .with("min_interval", ConfigValue::Duration(self.min_interval))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.last_tick = None;
+        self.last_result = None;
+        self.suspended_at = None;
+        self.child.reset();
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        self.child.suspend(memory);
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some(last), Some(suspended_at)) = (&mut self.last_tick, self.suspended_at.take()) {
+            *last += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        self.child.resume(memory);
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::config::NodeConfig;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::rate_limiter::RateLimiter;
+    use crate::tests::{MockNode, context_at};
+
+    #[test]
+    fn test_child_ticked_once_per_interval() {
+        let clock = ManualClock::new();
+        let scan = MockNode::new("scan", vec![NodeResult::Failed, NodeResult::Passed]);
+        let probe = scan.probe();
+        let mut node = RateLimiter::new("perceive".into(), Duration::from_millis(100), Box::new(scan))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 1);
+
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_reset_forwards_next_tick() {
+        let clock = ManualClock::new();
+        let scan = MockNode::new("scan", vec![NodeResult::Running]);
+        let probe = scan.probe();
+        let mut node = RateLimiter::new("perceive".into(), Duration::from_secs(1), Box::new(scan))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let node = RateLimiter::new("perceive".into(), Duration::from_millis(250), Box::new(MockNode::new("scan", vec![])));
+        let config: NodeConfig = node.to_config();
+        let rebuilt = RateLimiter::from_config(&config, Box::new(MockNode::new("scan", vec![]))).unwrap();
+        assert_eq!(rebuilt.min_interval, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let scan = MockNode::new("scan", vec![NodeResult::Running, NodeResult::Passed]);
+        let probe = scan.probe();
+        let mut node = RateLimiter::new("throttled".into(), Duration::from_secs(2), Box::new(scan))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Running);
+        assert_eq!(probe.ticks(), 1);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+}
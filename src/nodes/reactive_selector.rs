@@ -0,0 +1,282 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// A selector without memory: every tick starts again at the highest
+/// priority child, so a branch that becomes viable preempts a lower priority
+/// branch that is still running. The preempted branch is interrupted, as is
+/// the running branch when the selector finishes.
+pub struct ReactiveSelector {
+    pub children: Vec<Box<dyn Node>>,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+/// The selector without memory, under the name that pairs it with
+/// `Selector`.
+pub type SelectorStar = ReactiveSelector;
+
+impl ReactiveSelector {
+    pub fn new(name: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self { children, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    /// Appends `child` as the lowest-priority option, considered from the next tick on.
+    pub fn add_child(&mut self, child: Box<dyn Node>) {
+        self.children.push(child);
+    }
+
+    /// Takes out the child at `index`. Removing the running child resets the
+    /// selector first.
+    pub fn remove_child(&mut self, index: usize) -> Option<Box<dyn Node>> {
+        if index >= self.children.len() {
+            return None;
+        }
+        match self.current_child {
+            Some(running) if running == index => self.reset(),
+            Some(running) if running > index => self.current_child = Some(running - 1),
+            _ => { }
+        }
+        Some(self.children.remove(index))
+    }
+
+    fn finish(&mut self, running: Option<usize>, result: NodeResult) -> NodeResult {
+        if let Some(previous) = self.current_child
+            && running != Some(previous) {
+            self.children[previous].interrupt();
+        }
+        self.current_child = running;
+        result
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        for index in 0..self.children.len() {
+            match tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Failed => continue,
+                NodeResult::Running => return self.finish(Some(index), NodeResult::Running),
+                NodeResult::Passed => return self.finish(None, NodeResult::Passed)
+            }
+        }
+        self.finish(None, NodeResult::Failed)
+    }
+}
+
+impl Node for ReactiveSelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn interrupt(&mut self) {
+        if let Some(index) = self.current_child.take() {
+            self.children[index].interrupt();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeId, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::reactive_selector::{ReactiveSelector, SelectorStar};
+    use crate::nodes::selector::Selector;
+    use crate::tests::MockNode;
+    use crate::tree::BehaviouralTree;
+
+    /// Always running; counts interrupts separately from resets.
+    struct Grasp {
+        interrupts: Arc<AtomicUsize>,
+        resets: Arc<AtomicUsize>,
+        id: NodeId
+    }
+
+    impl Node for Grasp {
+        fn tick(&mut self, _memory: &mut BlackBoard) -> NodeResult {
+            NodeResult::Running
+        }
+
+        fn interrupt(&mut self) {
+            self.interrupts.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn reset(&mut self) {
+            self.resets.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn get_name(&self) -> String {
+            "grasp".into()
+        }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_higher_priority_branch_preempts() {
+        let flee = Action::new("flee".into(), |bb| match bb.get::<bool>("low_health").copied().unwrap_or(false) {
+            true => NodeResult::Running,
+            false => NodeResult::Failed
+        });
+        let patrol = MockNode::new("patrol", vec![NodeResult::Running]);
+        let probe = patrol.probe();
+        let mut node = ReactiveSelector::new("behaviour".into(), vec![Box::new(flee), Box::new(patrol)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "patrol");
+        assert_eq!(probe.resets(), 0);
+
+        memory.set("low_health", true);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "flee");
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(probe.resets(), 1);
+
+        memory.set("low_health", false);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "patrol");
+    }
+
+    #[test]
+    fn test_passing_branch_resets_running_one() {
+        let first = MockNode::new("first", vec![NodeResult::Failed, NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running]);
+        let probe = second.probe();
+        let mut node = ReactiveSelector::new("options".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.resets(), 1);
+        assert!(node.running_child().is_none());
+    }
+
+    #[test]
+    fn test_preemption_interrupts_running_branch() {
+        let (interrupts, resets) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let grasp = Grasp { interrupts: interrupts.clone(), resets: resets.clone(), id: NodeId::next() };
+        let alarm = Action::new("alarm".into(), |bb| match bb.get::<bool>("alarm").copied().unwrap_or(false) {
+            true => NodeResult::Running,
+            false => NodeResult::Failed
+        });
+        let mut node = ReactiveSelector::new("behaviour".into(), vec![Box::new(alarm), Box::new(grasp)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        memory.set("alarm", true);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(interrupts.load(Ordering::SeqCst), 1);
+        assert_eq!(resets.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_tree_interrupt_reaches_running_child_only() {
+        let (interrupts, resets) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let idle = MockNode::new("idle", vec![NodeResult::Failed]);
+        let probe = idle.probe();
+        let grasp = Grasp { interrupts: interrupts.clone(), resets: resets.clone(), id: NodeId::next() };
+        let root = ReactiveSelector::new("behaviour".into(), vec![Box::new(idle), Box::new(grasp)]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        tree.interrupt();
+        assert_eq!(interrupts.load(Ordering::SeqCst), 1);
+        assert_eq!(probe.resets(), 0);
+        assert_eq!(tree.last_result, None);
+        assert!(tree.root.running_child().is_none());
+
+        tree.interrupt();
+        assert_eq!(interrupts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_remove_child_shifts_running_branch() {
+        let alarm = MockNode::new("alarm", vec![NodeResult::Failed]);
+        let patrol = MockNode::new("patrol", vec![NodeResult::Running]);
+        let probe = patrol.probe();
+        let mut node = ReactiveSelector::new("behaviour".into(), vec![Box::new(alarm), Box::new(patrol)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        node.remove_child(0);
+        assert_eq!(node.running_child().unwrap().get_name(), "patrol");
+        node.add_child(Box::new(MockNode::new("idle", vec![NodeResult::Passed])));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.resets(), 0);
+
+        node.remove_child(0);
+        assert_eq!(probe.resets(), 1);
+        assert!(node.running_child().is_none());
+    }
+
+    #[test]
+    fn test_star_rechecks_where_selector_resumes() {
+        fn options() -> (MockNode, MockNode) {
+            (MockNode::new("flee", vec![NodeResult::Failed]), MockNode::new("walk", vec![NodeResult::Running, NodeResult::Passed]))
+        }
+        let mut memory = BlackBoard::new();
+
+        let (flee, walk) = options();
+        let probe = flee.probe();
+        let mut with_memory = Selector::new("options".into(), vec![Box::new(flee), Box::new(walk)]);
+        assert_eq!(with_memory.tick(&mut memory), NodeResult::Running);
+        assert_eq!(with_memory.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 1);
+
+        let (flee, walk) = options();
+        let probe = flee.probe();
+        let mut star = SelectorStar::new("options".into(), vec![Box::new(flee), Box::new(walk)]);
+        assert_eq!(star.tick(&mut memory), NodeResult::Running);
+        assert_eq!(star.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+}
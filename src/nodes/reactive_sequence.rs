@@ -0,0 +1,199 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// A sequence without memory: every tick starts again at the first child, so
+/// a guard that stops passing aborts the action running after it. When the
+/// running child changes or the sequence finishes, the child that was
+/// running before is interrupted.
+pub struct ReactiveSequence {
+    pub children: Vec<Box<dyn Node>>,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+/// The sequence without memory, under the name that pairs it with
+/// `Sequence`.
+pub type SequenceStar = ReactiveSequence;
+
+impl ReactiveSequence {
+    pub fn new(name: String, children: Vec<Box<dyn Node>>) -> Self {
+        Self { children, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    /// Appends `child` as the last condition or step, checked from the next tick on.
+    pub fn add_child(&mut self, child: Box<dyn Node>) {
+        self.children.push(child);
+    }
+
+    /// Takes out the child at `index`. Removing the running child resets the
+    /// sequence first.
+    pub fn remove_child(&mut self, index: usize) -> Option<Box<dyn Node>> {
+        if index >= self.children.len() {
+            return None;
+        }
+        match self.current_child {
+            Some(running) if running == index => self.reset(),
+            Some(running) if running > index => self.current_child = Some(running - 1),
+            _ => { }
+        }
+        Some(self.children.remove(index))
+    }
+
+    fn finish(&mut self, running: Option<usize>, result: NodeResult) -> NodeResult {
+        if let Some(previous) = self.current_child
+            && running != Some(previous) {
+            self.children[previous].interrupt();
+        }
+        self.current_child = running;
+        result
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        for index in 0..self.children.len() {
+            match tick_child_with_error(self.children[index].as_mut(), memory, &mut trace, &mut self.error) {
+                NodeResult::Passed => continue,
+                NodeResult::Running => return self.finish(Some(index), NodeResult::Running),
+                NodeResult::Failed => return self.finish(None, NodeResult::Failed)
+            }
+        }
+        self.finish(None, NodeResult::Passed)
+    }
+}
+
+impl Node for ReactiveSequence {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn interrupt(&mut self) {
+        if let Some(index) = self.current_child.take() {
+            self.children[index].interrupt();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::condition::Condition;
+    use crate::nodes::fallible_action::FallibleAction;
+    use crate::nodes::reactive_sequence::{ReactiveSequence, SequenceStar};
+    use crate::nodes::sequence::Sequence;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_failing_guard_aborts_running_action() {
+        let guard = Condition::new("enemy_visible".into(), |bb| bb.get::<bool>("enemy_visible").copied().unwrap_or(false));
+        let attack = MockNode::new("attack", vec![NodeResult::Running]);
+        let probe = attack.probe();
+        let mut node = ReactiveSequence::new("engage".into(), vec![Box::new(guard), Box::new(attack)]);
+        let mut memory = BlackBoard::new();
+        memory.set("enemy_visible", true);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "attack");
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(probe.resets(), 0);
+
+        memory.set("enemy_visible", false);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(probe.resets(), 1);
+        assert!(node.running_child().is_none());
+    }
+
+    #[test]
+    fn test_rechecks_earlier_children() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running, NodeResult::Passed]);
+        let first_probe = first.probe();
+        let mut node = ReactiveSequence::new("steps".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(first_probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_star_restarts_where_sequence_resumes() {
+        fn steps() -> (MockNode, MockNode) {
+            (MockNode::new("check", vec![NodeResult::Passed]), MockNode::new("walk", vec![NodeResult::Running, NodeResult::Passed]))
+        }
+        let mut memory = BlackBoard::new();
+
+        let (check, walk) = steps();
+        let probe = check.probe();
+        let mut with_memory = Sequence::new("steps".into(), vec![Box::new(check), Box::new(walk)]);
+        assert_eq!(with_memory.tick(&mut memory), NodeResult::Running);
+        assert_eq!(with_memory.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 1);
+
+        let (check, walk) = steps();
+        let probe = check.probe();
+        let mut star = SequenceStar::new("steps".into(), vec![Box::new(check), Box::new(walk)]);
+        assert_eq!(star.tick(&mut memory), NodeResult::Running);
+        assert_eq!(star.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_failed_child_error_is_kept() {
+        fn jammed(_: &mut BlackBoard) -> Result<NodeResult, String> {
+            Err("door jammed".into())
+        }
+        let mut node = ReactiveSequence::new("enter".into(), vec![
+            Box::new(Condition::new("door_closed".into(), |_| true)),
+            Box::new(FallibleAction::new("open".into(), jammed)),
+        ]);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+        assert_eq!(node.last_error().as_deref(), Some("door jammed"));
+    }
+}
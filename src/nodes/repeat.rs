@@ -0,0 +1,210 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// What `Repeat` does once its child fails. A passing child is always
+/// started again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepeatMode {
+    /// Loops whatever the child returns; the repeat never finishes.
+    Forever,
+    /// Loops until the child fails, then passes.
+    UntilFail,
+    /// Loops while the child passes and fails as soon as it fails.
+    WhileSuccess
+}
+
+impl RepeatMode {
+    /// Reads the mode under `key`, `Forever` when it is not given.
+    fn from_config(config: &NodeConfig, key: &str) -> Result<Self, String> {
+        if !config.params.contains_key(key) {
+            return Ok(RepeatMode::Forever);
+        }
+        Ok(match config.text(key)?.as_str() {
+            "forever" => RepeatMode::Forever,
+            "until_fail" => RepeatMode::UntilFail,
+            "while_success" => RepeatMode::WhileSuccess,
+            other => return Err(format!("Repeat '{}' has unknown mode '{other}'", config.name))
+        })
+    }
+
+    fn to_config(self, config: NodeConfig, key: &str) -> NodeConfig {
+        let mode = match self {
+            RepeatMode::Forever => "forever",
+            RepeatMode::UntilFail => "until_fail",
+            RepeatMode::WhileSuccess => "while_success"
+        };
+        config.with(key, ConfigValue::Text(mode.into()))
+    }
+}
+
+/// Runs its child over and over, one run at a time: each finished run resets
+/// the child and returns `Running`, so the next tick starts it again. The
+/// `mode` decides whether a failed run ends the loop and with what result.
+pub struct Repeat {
+    pub child: Box<dyn Node>,
+    pub mode: RepeatMode,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Repeat {
+    pub fn new(name: String, child: Box<dyn Node>, mode: RepeatMode) -> Self {
+        Self { child, mode, error: None, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("Repeat")?;
+        Ok(Self::new(config.name.clone(), child, RepeatMode::from_config(config, "mode")?))
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        if result == NodeResult::Running {
+            return NodeResult::Running;
+        }
+
+        self.child.reset();
+        match (result, self.mode) {
+            (NodeResult::Failed, RepeatMode::UntilFail) => NodeResult::Passed,
+            (NodeResult::Failed, RepeatMode::WhileSuccess) => NodeResult::Failed,
+            _ => NodeResult::Running
+        }
+    }
+}
+
+impl Node for Repeat {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        self.mode.to_config(NodeConfig::new("Repeat".into(), self.name.clone()), "mode")
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::fallible_action::FallibleAction;
+    use crate::nodes::repeat::{Repeat, RepeatMode};
+    use crate::tests::MockNode;
+
+    /// Passes on the first run, fails on the second and passes after that.
+    fn patrol(mode: RepeatMode) -> Repeat {
+        let mut runs = 0;
+        let lap = Action::new("lap".into(), move |bb| {
+            runs += 1;
+            bb.set("runs", runs);
+            match runs {
+                2 => NodeResult::Failed,
+                _ => NodeResult::Passed
+            }
+        });
+        Repeat::new("patrol".into(), Box::new(lap), mode)
+    }
+
+    #[test]
+    fn test_forever_ignores_failure() {
+        let mut node = patrol(RepeatMode::Forever);
+        let mut memory = BlackBoard::new();
+
+        for _ in 0..4 {
+            assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        }
+        assert_eq!(memory.get::<i32>("runs"), Some(&4));
+    }
+
+    #[test]
+    fn test_until_fail_passes_on_failure() {
+        let mut node = patrol(RepeatMode::UntilFail);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<i32>("runs"), Some(&2));
+    }
+
+    #[test]
+    fn test_while_success_fails_on_failure() {
+        let mut node = patrol(RepeatMode::WhileSuccess);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.get_name(), "patrol");
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(memory.get::<i32>("runs"), Some(&2));
+    }
+
+    #[test]
+    fn test_running_child_is_not_reset() {
+        let walk = MockNode::new("walk", vec![NodeResult::Running, NodeResult::Passed]);
+        let probe = walk.probe();
+        let mut node = Repeat::new("patrol".into(), Box::new(walk), RepeatMode::Forever);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.resets(), 0);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let node = patrol(RepeatMode::UntilFail);
+        let rebuilt = Repeat::from_config(&node.to_config(), Box::new(MockNode::new("lap", vec![]))).unwrap();
+        assert_eq!(rebuilt.mode, RepeatMode::UntilFail);
+    }
+
+    #[test]
+    fn test_error_survives_child_reset() {
+        fn jammed(_: &mut BlackBoard) -> Result<NodeResult, String> {
+            Err("door jammed".into())
+        }
+        let mut node = Repeat::new("retry_door".into(), Box::new(FallibleAction::new("open".into(), jammed)), RepeatMode::WhileSuccess);
+
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+        assert_eq!(node.last_error().as_deref(), Some("door jammed"));
+    }
+}
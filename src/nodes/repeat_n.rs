@@ -0,0 +1,133 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Runs its child until it has passed `count` times, then passes. After each
+/// pass the child is reset and run again straight away in the same tick, so
+/// only a `Running` child spreads the repetitions over several ticks. A
+/// failure fails the decorator at once and starts the count over. With a
+/// `count` of zero the child is never ticked.
+pub struct RepeatN {
+    pub child: Box<dyn Node>,
+    pub count: usize,
+    pub current: usize,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl RepeatN {
+    pub fn new(name: String, count: usize, child: Box<dyn Node>) -> Self {
+        Self { child, count, current: 0, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        while self.current < self.count {
+            match tick_child(self.child.as_mut(), memory, &mut trace) {
+                NodeResult::Passed => {
+                    self.current += 1;
+                    self.child.reset();
+                },
+                NodeResult::Running => return NodeResult::Running,
+                NodeResult::Failed => {
+                    self.current = 0;
+                    return NodeResult::Failed;
+                }
+            }
+        }
+        self.current = 0;
+        NodeResult::Passed
+    }
+}
+
+impl Node for RepeatN {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.current = 0;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::repeat_n::RepeatN;
+    use crate::tests::MockNode;
+
+    // Fails on the third waypoint, passes on every other one.
+    fn visit(memory: &mut BlackBoard) -> NodeResult {
+        let visited = memory.get::<u32>("visited").copied().unwrap_or(0) + 1;
+        memory.set("visited", visited);
+        if visited == 3 { NodeResult::Failed } else { NodeResult::Passed }
+    }
+
+    #[test]
+    fn test_zero_count_passes_without_ticking() {
+        let child = MockNode::new("waypoint", vec![NodeResult::Failed]);
+        let probe = child.probe();
+        let mut node = RepeatN::new("patrol".into(), 0, Box::new(child));
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 0);
+    }
+
+    #[test]
+    fn test_single_count() {
+        let child = MockNode::new("waypoint", vec![NodeResult::Running, NodeResult::Passed]);
+        let probe = child.probe();
+        let mut node = RepeatN::new("patrol".into(), 1, Box::new(child));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(node.current, 0);
+    }
+
+    #[test]
+    fn test_failure_midway() {
+        let mut node = RepeatN::new("patrol".into(), 4, Box::new(Action::new("visit".into(), visit)));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(memory.get::<u32>("visited"), Some(&3));
+        assert_eq!(node.current, 0);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<u32>("visited"), Some(&7));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Runs its child again and again while `condition` holds, e.g. keep
+/// patrolling while the alert level is low. The condition is checked before
+/// each run starts, never while the child is running, and the repeat passes
+/// once it no longer holds. Each pass resets the child and returns
+/// `Running`, so there is one run per tick at most; a failure fails the
+/// repeat.
+pub struct RepeatWhile {
+    pub condition: Box<dyn FnMut(&mut BlackBoard) -> bool + Send + Sync>,
+    pub child: Box<dyn Node>,
+    pub is_running: bool,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl RepeatWhile {
+    pub fn new(name: String, condition: impl FnMut(&mut BlackBoard) -> bool + Send + Sync + 'static, child: Box<dyn Node>) -> Self {
+        Self { condition: Box::new(condition), child, is_running: false, error: None, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        if !self.is_running && !(self.condition)(memory) {
+            return NodeResult::Passed;
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        self.is_running = result == NodeResult::Running;
+        match result {
+            NodeResult::Passed => {
+                self.child.reset();
+                NodeResult::Running
+            },
+            NodeResult::Failed => {
+                self.child.reset();
+                NodeResult::Failed
+            },
+            NodeResult::Running => NodeResult::Running
+        }
+    }
+}
+
+impl Node for RepeatWhile {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.is_running = false;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::repeat_while::RepeatWhile;
+    use crate::tests::MockNode;
+
+    fn calm(memory: &mut BlackBoard) -> bool {
+        memory.get::<u32>("alert").copied().unwrap_or(0) < 3
+    }
+
+    #[test]
+    fn test_loops_until_condition_flips() {
+        let lap = Action::new("lap".into(), |bb| {
+            let laps = bb.get::<u32>("laps").copied().unwrap_or(0) + 1;
+            bb.set("laps", laps);
+            NodeResult::Passed
+        });
+        let mut node = RepeatWhile::new("patrol".into(), calm, Box::new(lap));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        memory.set("alert", 5_u32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<u32>("laps"), Some(&2));
+    }
+
+    #[test]
+    fn test_condition_not_checked_mid_run() {
+        let walk = MockNode::new("walk", vec![NodeResult::Running, NodeResult::Passed]);
+        let probe = walk.probe();
+        let mut node = RepeatWhile::new("patrol".into(), calm, Box::new(walk));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        memory.set("alert", 5_u32);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.ticks(), 2);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_failure_ends_loop() {
+        let walk = MockNode::new("walk", vec![NodeResult::Failed]);
+        let mut node = RepeatWhile::new("patrol".into(), calm, Box::new(walk));
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Gives a flaky child up to `max_attempts` tries. A failed attempt resets
+/// the child and returns `Running`, so the next tick tries again; once
+/// `max_attempts` attempts have failed the failure is passed on. `Passed`
+/// is returned as is. Either outcome starts the attempt count over.
+pub struct Retry {
+    pub child: Box<dyn Node>,
+    pub max_attempts: usize,
+    pub attempts: usize,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Retry {
+    pub fn new(name: String, max_attempts: usize, child: Box<dyn Node>) -> Self {
+        Self { child, max_attempts, attempts: 0, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        match tick_child(self.child.as_mut(), memory, &mut trace) {
+            NodeResult::Passed => {
+                self.attempts = 0;
+                NodeResult::Passed
+            },
+            NodeResult::Failed => {
+                self.attempts += 1;
+                if self.attempts >= self.max_attempts {
+                    self.attempts = 0;
+                    return NodeResult::Failed;
+                }
+                self.child.reset();
+                NodeResult::Running
+            },
+            NodeResult::Running => NodeResult::Running
+        }
+    }
+}
+
+impl Node for Retry {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.attempts = 0;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::retry::Retry;
+
+    // Fails until the given attempt, then passes.
+    fn grasp(memory: &mut BlackBoard, succeeds_on: u32) -> NodeResult {
+        let attempt = memory.get::<u32>("attempt").copied().unwrap_or(0) + 1;
+        memory.set("attempt", attempt);
+        if attempt >= succeeds_on { NodeResult::Passed } else { NodeResult::Failed }
+    }
+
+    #[test]
+    fn test_passes_on_third_attempt() {
+        let mut node = Retry::new("retry".into(), 3, Box::new(Action::new("grasp".into(), |bb| grasp(bb, 3))));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.attempts, 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.attempts, 0);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let mut node = Retry::new("retry".into(), 3, Box::new(Action::new("grasp".into(), |bb| grasp(bb, 4))));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(memory.get::<u32>("attempt"), Some(&3));
+    }
+}
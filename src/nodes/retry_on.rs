@@ -0,0 +1,142 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+pub type ErrorFilter = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Retries its child, up to `max_retries` times in a row, when it fails with
+/// an error (see `Node::last_error`) accepted by `filter`. A retry resets the
+/// child and returns `Running`, so the next tick starts a fresh attempt.
+/// Failures without an error, failures the filter rejects and the failure
+/// that exhausts the retries are passed on at once.
+pub struct RetryOn {
+    pub child: Box<dyn Node>,
+    pub filter: ErrorFilter,
+    pub max_retries: usize,
+    pub retries: usize,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl RetryOn {
+    pub fn new(name: String, max_retries: usize, filter: ErrorFilter, child: Box<dyn Node>) -> Self {
+        Self { child, filter, max_retries, retries: 0, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        match tick_child(self.child.as_mut(), memory, &mut trace) {
+            NodeResult::Failed => {
+                let retryable = self.child.last_error().is_some_and(|error| (self.filter)(&error));
+                if retryable && self.retries < self.max_retries {
+                    self.retries += 1;
+                    self.child.reset();
+                    return NodeResult::Running;
+                }
+                self.retries = 0;
+                NodeResult::Failed
+            },
+            NodeResult::Passed => {
+                self.retries = 0;
+                NodeResult::Passed
+            },
+            NodeResult::Running => NodeResult::Running
+        }
+    }
+}
+
+impl Node for RetryOn {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.retries = 0;
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::fallible_action::FallibleAction;
+    use crate::nodes::retry_on::RetryOn;
+
+    // Fails with the errors queued under "errors", front first, then passes.
+    fn request(memory: &mut BlackBoard) -> Result<NodeResult, String> {
+        let errors = memory.data.get_mut("errors").and_then(|value| value.downcast_mut::<Vec<&str>>()).unwrap();
+        if errors.is_empty() {
+            return Ok(NodeResult::Passed);
+        }
+        Err(errors.remove(0).to_string())
+    }
+
+    fn node() -> RetryOn {
+        RetryOn::new("retry".into(), 3, Box::new(|error| error.starts_with("timeout")), Box::new(FallibleAction::new("request".into(), request)))
+    }
+
+    #[test]
+    fn test_transient_error_is_retried() {
+        let mut node = node();
+        let mut memory = BlackBoard::new();
+        memory.data.insert("errors".into(), Box::new(vec!["timeout", "timeout"]));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_permanent_error_is_propagated() {
+        let mut node = node();
+        let mut memory = BlackBoard::new();
+        memory.data.insert("errors".into(), Box::new(vec!["timeout", "unauthorized", "timeout"]));
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(node.last_error().as_deref(), Some("unauthorized"));
+    }
+
+    #[test]
+    fn test_retries_are_bounded() {
+        let mut node = node();
+        let mut memory = BlackBoard::new();
+        memory.data.insert("errors".into(), Box::new(vec!["timeout"; 5]));
+
+        for _ in 0..3 {
+            assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        }
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+}
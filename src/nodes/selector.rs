@@ -1,11 +1,21 @@
-use crate::node::Node;
+use crate::node::{Node, NodeId, Trace, tick_child};
 use crate::blackboard::BlackBoard;
 use crate::node::NodeResult;
+
+/// Ticks its children in order until one doesn't fail. A child that returns
+/// `Running` is left as it is and the next tick resumes at that child,
+/// without re-checking the children before it.
+///
+/// This is the selector with memory. `SelectorStar` is the one without: it
+/// starts at the first child on every tick, so a higher priority child can
+/// take over from a running one.
 pub struct Selector {
     pub children: Vec<Box<dyn Node>>,
     pub current_child: i32,
+    pub id: NodeId,
     pub name: String,
-    pub is_running: bool
+    pub is_running: bool,
+    pub error: Option<String>
 }
 
 impl Selector {
@@ -13,44 +23,190 @@ impl Selector {
         Self {
             children,
             current_child: 0,
+            id: NodeId::next(),
             name,
-            is_running: false
+            is_running: false,
+            error: None
         }
     }
-}
 
-impl Node for Selector {
-    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
-        for child in &mut self.children {
-            let result = child.tick(memory);
+    pub fn with_id(mut self, id: NodeId) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Appends `child` as the last option; a running selector keeps its progress.
+    pub fn add_child(&mut self, child: Box<dyn Node>) {
+        self.children.push(child);
+    }
+
+    /// Takes out the child at `index`. Progress through the remaining
+    /// children is kept, unless the removed child was running, in which case
+    /// the selector is reset and starts over on the next tick.
+    pub fn remove_child(&mut self, index: usize) -> Option<Box<dyn Node>> {
+        if index >= self.children.len() {
+            return None;
+        }
+        if self.is_running {
+            match (index as i32).cmp(&self.current_child) {
+                std::cmp::Ordering::Less => self.current_child -= 1,
+                std::cmp::Ordering::Equal => self.reset(),
+                std::cmp::Ordering::Greater => { }
+            }
+        }
+        Some(self.children.remove(index))
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        if !self.is_running {
+            self.current_child = 0;
+        }
+        self.is_running = false;
+        self.error = None;
+        let start = self.current_child as usize;
+        for (index, child) in self.children.iter_mut().enumerate().skip(start) {
+            let result = tick_child(child.as_mut(), memory, &mut trace);
             match result {
                 NodeResult::Passed => {
                     child.reset();
+                    self.current_child = 0;
                     return NodeResult::Passed
                 },
                 NodeResult::Running => {
-                    child.reset();
+                    self.current_child = index as i32;
+                    self.is_running = true;
                     return NodeResult::Running
                 },
                 NodeResult::Failed => {
+                    self.error = child.last_error();
                     self.current_child += 1;
                     continue
                 }
             }
         }
+        self.current_child = 0;
         NodeResult::Failed
     }
+}
+
+impl Node for Selector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
 
     fn get_name(&self) -> String {
         self.name.clone()
     }
 
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn interrupt(&mut self) {
+        if self.is_running
+            && let Some(child) = self.children.get_mut(self.current_child as usize) {
+            child.interrupt();
+        }
+        self.current_child = 0;
+        self.is_running = false;
+        self.error = None;
+    }
+
     fn reset(&mut self) {
         self.current_child = 0;
         self.is_running = false;
+        self.error = None;
 
         for child in &mut self.children {
             child.reset();
         }
     }
-}
\ No newline at end of file
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        if !self.is_running {
+            return None;
+        }
+        self.children.get(self.current_child as usize).map(|child| child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::selector::Selector;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_running_child_keeps_progress() {
+        let first = MockNode::new("first", vec![NodeResult::Failed]);
+        let second = MockNode::new("second", vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+        let (first_probe, second_probe) = (first.probe(), second.probe());
+        let mut node = Selector::new("options".into(), vec![Box::new(first), Box::new(second)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.current_child, 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(second_probe.ticks(), 3);
+        assert_eq!(first_probe.ticks(), 1);
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(first_probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_remove_child_keeps_progress() {
+        let first = MockNode::new("first", vec![NodeResult::Failed]);
+        let second = MockNode::new("second", vec![NodeResult::Running, NodeResult::Passed]);
+        let third = MockNode::new("third", vec![NodeResult::Passed]);
+        let (second_probe, third_probe) = (second.probe(), third.probe());
+        let mut node = Selector::new("options".into(), vec![Box::new(first), Box::new(second), Box::new(third)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.remove_child(0).unwrap().get_name(), "first");
+        assert_eq!(node.running_child().unwrap().get_name(), "second");
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!((second_probe.ticks(), third_probe.ticks()), (2, 0));
+        assert!(node.remove_child(5).is_none());
+    }
+
+    #[test]
+    fn test_remove_running_child_resets() {
+        let first = MockNode::new("first", vec![NodeResult::Failed]);
+        let second = MockNode::new("second", vec![NodeResult::Running]);
+        let third = MockNode::new("third", vec![NodeResult::Passed]);
+        let (first_probe, second_probe, third_probe) = (first.probe(), second.probe(), third.probe());
+        let mut node = Selector::new("options".into(), vec![Box::new(first), Box::new(second), Box::new(third)]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        node.remove_child(1);
+        assert_eq!(second_probe.resets(), 1);
+        assert!(node.running_child().is_none());
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!((first_probe.ticks(), third_probe.ticks()), (2, 1));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult};
+
+/// A leaf that keeps its own progress, e.g. the index of the next waypoint
+/// of a path, instead of storing it on the blackboard. Implement this and
+/// the type is a `Node`; `reset` must put the progress back to the start.
+pub trait StatefulAction: Send + Sync + 'static {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult;
+
+    fn reset(&mut self);
+
+    fn name(&self) -> String;
+
+    /// An id taken from `NodeId::next` when the action was created.
+    fn id(&self) -> NodeId;
+}
+
+impl<T: StatefulAction> Node for T {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        StatefulAction::tick(self, memory)
+    }
+
+    fn reset(&mut self) {
+        StatefulAction::reset(self);
+    }
+
+    fn get_name(&self) -> String {
+        self.name()
+    }
+
+    fn id(&self) -> NodeId {
+        StatefulAction::id(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeId, NodeResult};
+    use crate::nodes::sequence::Sequence;
+    use crate::nodes::stateful_action::StatefulAction;
+
+    /// Runs for `steps` ticks, counting them itself.
+    struct MoveTo {
+        steps: u32,
+        ticks: u32,
+        id: NodeId
+    }
+
+    impl StatefulAction for MoveTo {
+        fn tick(&mut self, _memory: &mut BlackBoard) -> NodeResult {
+            self.ticks += 1;
+            match self.ticks >= self.steps {
+                true => NodeResult::Passed,
+                false => NodeResult::Running
+            }
+        }
+
+        fn reset(&mut self) {
+            self.ticks = 0;
+        }
+
+        fn name(&self) -> String {
+            "move_to".into()
+        }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_counts_ticks_and_resets() {
+        let mut node: Box<dyn Node> = Box::new(MoveTo { steps: 3, ticks: 0, id: NodeId::next() });
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.get_name(), "move_to");
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(memory.is_empty());
+    }
+
+    #[test]
+    fn test_used_inside_composite() {
+        let mut node = Sequence::new("patrol".into(), vec![Box::new(MoveTo { steps: 2, ticks: 0, id: NodeId::next() })]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::mpsc::Sender;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Sends its child's result over a channel after every tick so another
+/// thread can follow the subtree live. Once the receiver hangs up the
+/// sender is dropped and the node just passes results through.
+pub struct StreamStatus {
+    pub child: Box<dyn Node>,
+    pub sender: Option<Sender<NodeResult>>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl StreamStatus {
+    pub fn new(name: String, sender: Sender<NodeResult>, child: Box<dyn Node>) -> Self {
+        Self { child, sender: Some(sender), id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        if let Some(sender) = &self.sender
+            && sender.send(result.clone()).is_err() {
+            self.sender = None;
+        }
+        result
+    }
+}
+
+impl Node for StreamStatus {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::stream_status::StreamStatus;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_results_are_streamed_in_order() {
+        let (sender, receiver) = mpsc::channel();
+        let child = MockNode::new("work", vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+        let mut node = StreamStatus::new("stream".into(), sender, Box::new(child));
+
+        let consumer = thread::spawn(move || receiver.iter().collect::<Vec<_>>());
+        let mut memory = BlackBoard::new();
+        for _ in 0..3 {
+            node.tick(&mut memory);
+        }
+        drop(node);
+
+        assert_eq!(consumer.join().unwrap(), vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+    }
+
+    #[test]
+    fn test_disconnected_receiver_is_ignored() {
+        let (sender, receiver) = mpsc::channel();
+        let child = MockNode::new("work", vec![NodeResult::Failed]);
+        let mut node = StreamStatus::new("stream".into(), sender, Box::new(child));
+        drop(receiver);
+
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert!(node.sender.is_none());
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+}
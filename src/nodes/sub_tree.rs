@@ -0,0 +1,158 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Runs a reusable tree as a black box against its own blackboard, so its
+/// keys can't clash with the rest of the tree. Values are only exchanged
+/// through `ports`, `(parent_key, child_key)` pairs: before each tick the
+/// value under every `parent_key` is moved to its `child_key`, and after
+/// the tick every `child_key` is moved back, changes included. Moving
+/// instead of copying keeps arbitrary `Any` values usable as ports.
+/// Writing a value back counts as a change of the parent's key, like `set`.
+pub struct SubTree {
+    pub root: Box<dyn Node>,
+    pub memory: BlackBoard,
+    pub ports: Vec<(String, String)>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl SubTree {
+    pub fn new(name: String, ports: Vec<(String, String)>, root: Box<dyn Node>) -> Self {
+        Self { root, memory: BlackBoard::new(), ports, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.memory.context = memory.context;
+        for (parent_key, child_key) in &self.ports {
+            if let Some((value, expiry)) = memory.take_entry(parent_key) {
+                self.memory.insert_entry(child_key, value, expiry);
+            }
+        }
+        let result = tick_child(self.root.as_mut(), &mut self.memory, &mut trace);
+        for (parent_key, child_key) in &self.ports {
+            if let Some((value, expiry)) = self.memory.take_entry(child_key) {
+                memory.insert_entry(parent_key, value, expiry);
+            }
+        }
+        self.memory.context = None;
+        result
+    }
+}
+
+impl Node for SubTree {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.root.reset();
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.root.last_error()
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.root.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.root.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.root.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::TickContext;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::sub_tree::SubTree;
+    use crate::nodes::wait::Wait;
+
+    fn navigate(memory: &mut BlackBoard) -> NodeResult {
+        let Some(goal) = memory.get::<(i32, i32)>("goal").copied() else {
+            return NodeResult::Failed;
+        };
+        memory.set("target", "scratch");
+        memory.set("path_length", goal.0.abs() + goal.1.abs());
+        NodeResult::Passed
+    }
+
+    #[test]
+    fn test_ports_and_isolation() {
+        let ports = vec![("door".into(), "goal".into()), ("distance".into(), "path_length".into())];
+        let mut node = SubTree::new("go_to_door".into(), ports, Box::new(Action::new("navigate".into(), navigate)));
+        let mut memory = BlackBoard::new();
+        memory.set("door", (3, -4));
+        memory.set("target", "enemy");
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<i32>("distance"), Some(&7));
+        assert_eq!(memory.get::<(i32, i32)>("door"), Some(&(3, -4)));
+        assert_eq!(memory.get::<&str>("target"), Some(&"enemy"));
+        assert!(!memory.contains_key("goal"));
+        assert_eq!(node.memory.get::<&str>("target"), Some(&"scratch"));
+    }
+
+    #[test]
+    fn test_tick_context_reaches_subtree() {
+        let mut node = SubTree::new("pause".into(), vec![], Box::new(Wait::new("wait".into(), Duration::from_secs(2))));
+        let mut memory = BlackBoard::new();
+        let start = Instant::now() + Duration::from_secs(3600);
+        let dt = Duration::from_secs(1);
+
+        assert_eq!(node.tick_ctx(&mut memory, &TickContext::new(start, dt)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &TickContext::new(start + dt, dt)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &TickContext::new(start + dt * 2, dt)), NodeResult::Passed);
+        assert!(TickContext::current(&node.memory).is_none());
+    }
+
+    #[test]
+    fn test_write_back_is_observed() {
+        let ports = vec![("door".into(), "goal".into()), ("distance".into(), "path_length".into())];
+        let mut node = SubTree::new("go_to_door".into(), ports, Box::new(Action::new("navigate".into(), navigate)));
+        let mut memory = BlackBoard::new();
+        memory.set("door", (3, -4));
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let seen = changed.clone();
+        memory.on_change(Box::new(move |key| seen.lock().unwrap().push(key.to_string())));
+        let version = memory.version();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(memory.version() > version);
+        let mut changed = changed.lock().unwrap().clone();
+        changed.sort();
+        assert_eq!(changed, vec!["distance", "door"]);
+    }
+
+    #[test]
+    fn test_missing_input_port() {
+        let ports = vec![("door".into(), "goal".into())];
+        let mut node = SubTree::new("go_to_door".into(), ports, Box::new(Action::new("navigate".into(), navigate)));
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Makes an optional branch non-blocking: any finished result of the child
+/// becomes `Passed`, while `Running` is passed through.
+pub struct Succeeder {
+    pub child: Box<dyn Node>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Succeeder {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        match tick_child(self.child.as_mut(), memory, &mut trace) {
+            NodeResult::Running => NodeResult::Running,
+            _ => NodeResult::Passed
+        }
+    }
+}
+
+impl Node for Succeeder {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::succeeder::Succeeder;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_succeeder_passes_and_keeps_running() {
+        let child = MockNode::new("optional", vec![NodeResult::Running, NodeResult::Passed, NodeResult::Failed]);
+        let probe = child.probe();
+        let mut node = Succeeder::new("succeeder".into(), Box::new(child));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+
+        node.reset();
+        assert_eq!(probe.resets(), 1);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Ticks the child registered for the `T` stored under `key`, e.g. one
+/// branch per variant of an AI state enum, and returns its result. When no
+/// case matches, including when the key is missing or holds another type,
+/// `default` is ticked instead, or the switch fails if there is none.
+pub struct Switch<T> {
+    pub cases: Vec<(T, Box<dyn Node>)>,
+    pub default: Option<Box<dyn Node>>,
+    pub key: String,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl<T: PartialEq + Send + Sync + 'static> Switch<T> {
+    pub fn new(name: String, key: String, cases: Vec<(T, Box<dyn Node>)>, default: Option<Box<dyn Node>>) -> Self {
+        Self { cases, default, key, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    /// Index of the matching case, or `cases.len()` for the default.
+    fn route(&self, memory: &BlackBoard) -> Option<usize> {
+        let matched = memory.get::<T>(&self.key)
+            .and_then(|value| self.cases.iter().position(|(case, _)| case == value));
+        match matched {
+            Some(index) => Some(index),
+            None => self.default.as_ref().map(|_| self.cases.len())
+        }
+    }
+
+    fn child_mut(&mut self, index: usize) -> &mut Box<dyn Node> {
+        match self.cases.get_mut(index) {
+            Some((_, child)) => child,
+            None => self.default.as_mut().unwrap()
+        }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let route = self.route(memory);
+        if let Some(previous) = self.current_child
+            && route != Some(previous) {
+            self.child_mut(previous).reset();
+        }
+
+        let Some(index) = route else {
+            self.current_child = None;
+            return NodeResult::Failed;
+        };
+        let mut error = None;
+        let result = tick_child_with_error(self.child_mut(index).as_mut(), memory, &mut trace, &mut error);
+        self.error = error;
+        self.current_child = match result {
+            NodeResult::Running => Some(index),
+            _ => None
+        };
+        result
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static> Node for Switch<T> {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for child in self.children_mut() {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        let index = self.current_child?;
+        match self.cases.get(index) {
+            Some((_, child)) => Some(child.as_ref()),
+            None => self.default.as_deref()
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.cases.iter().map(|(_, child)| child.as_ref()).chain(self.default.as_deref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        let mut children: Vec<&mut dyn Node> = self.cases.iter_mut().map(|(_, child)| child.as_mut() as &mut dyn Node).collect();
+        if let Some(default) = self.default.as_mut() {
+            children.push(default.as_mut());
+        }
+        children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::switch::Switch;
+
+    #[derive(PartialEq)]
+    enum EnemyState {
+        Patrol,
+        Chase,
+        Flee
+    }
+
+    fn mark(memory: &mut BlackBoard, branch: &'static str) -> NodeResult {
+        memory.set("branch", branch);
+        NodeResult::Passed
+    }
+
+    fn switch(with_default: bool) -> Switch<EnemyState> {
+        let default: Option<Box<dyn Node>> = match with_default {
+            true => Some(Box::new(Action::new("idle".into(), |bb| mark(bb, "idle")))),
+            false => None
+        };
+        Switch::new("state".into(), "enemy_state".into(), vec![
+            (EnemyState::Patrol, Box::new(Action::new("patrol".into(), |bb| mark(bb, "patrol")))),
+            (EnemyState::Chase, Box::new(Action::new("chase".into(), |_| NodeResult::Running))),
+        ], default)
+    }
+
+    #[test]
+    fn test_matched_case() {
+        let mut node = switch(false);
+        let mut memory = BlackBoard::new();
+        memory.set("enemy_state", EnemyState::Patrol);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"patrol"));
+
+        memory.set("enemy_state", EnemyState::Chase);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.running_child().unwrap().get_name(), "chase");
+    }
+
+    #[test]
+    fn test_unmatched_case_uses_default() {
+        let mut node = switch(true);
+        let mut memory = BlackBoard::new();
+        memory.set("enemy_state", EnemyState::Flee);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"idle"));
+        assert_eq!(node.children().len(), 3);
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let mut memory = BlackBoard::new();
+        assert_eq!(switch(false).tick(&mut memory), NodeResult::Failed);
+
+        memory.set("enemy_state", EnemyState::Flee);
+        assert_eq!(switch(false).tick(&mut memory), NodeResult::Failed);
+
+        memory.remove("enemy_state");
+        assert_eq!(switch(true).tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"idle"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Ticks every unfinished child each tick and passes once `required` of
+/// them have passed, all within a shared `budget` measured from the first
+/// tick. When the budget runs out, or too many children have failed for
+/// `required` to still be reachable, it fails. Either way the children that
+/// are still running get halted through `reset`.
+pub struct TimedParallel {
+    pub children: Vec<Box<dyn Node>>,
+    pub states: Vec<NodeResult>,
+    pub required: usize,
+    pub budget: Duration,
+    pub start: Option<Instant>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl TimedParallel {
+    pub fn new(name: String, children: Vec<Box<dyn Node>>, required: usize, budget: Duration) -> Self {
+        Self {
+            states: vec![NodeResult::Running; children.len()],
+            children,
+            required,
+            budget,
+            start: None,
+            suspended_at: None,
+            clock: Arc::new(SystemClock),
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn from_config(config: &NodeConfig, children: Vec<Box<dyn Node>>) -> Result<Self, String> {
+        config.expect_kind("TimedParallel")?;
+        Ok(Self::new(config.name.clone(), children, config.count("required")?, config.duration("budget")?))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn finish(&mut self, result: NodeResult) -> NodeResult {
+        if result != NodeResult::Failed {
+            self.error = None;
+        }
+        for (child, state) in self.children.iter_mut().zip(&mut self.states) {
+            if *state == NodeResult::Running {
+                child.reset();
+            }
+            *state = NodeResult::Running;
+        }
+        self.start = None;
+        result
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let start = *self.start.get_or_insert(now);
+        if now.duration_since(start) >= self.budget {
+            self.error = None;
+            return self.finish(NodeResult::Failed);
+        }
+
+        for (child, state) in self.children.iter_mut().zip(&mut self.states) {
+            if *state == NodeResult::Running {
+                *state = tick_child_with_error(child.as_mut(), memory, &mut trace, &mut self.error);
+            }
+        }
+
+        let passed = self.states.iter().filter(|state| **state == NodeResult::Passed).count();
+        let failed = self.states.iter().filter(|state| **state == NodeResult::Failed).count();
+        if passed >= self.required {
+            return self.finish(NodeResult::Passed);
+        }
+        if self.children.len() - failed < self.required {
+            return self.finish(NodeResult::Failed);
+        }
+        NodeResult::Running
+    }
+}
+
+impl Node for TimedParallel {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("TimedParallel".into(), self.name.clone())
+            .with("required", ConfigValue::Int(self.required as i64))
+            .with("budget", ConfigValue::Duration(self.budget))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.start = None;
+        self.suspended_at = None;
+
+        for (child, state) in self.children.iter_mut().zip(&mut self.states) {
+            child.reset();
+            *state = NodeResult::Running;
+        }
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        for child in &mut self.children {
+            child.suspend(memory);
+        }
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some(start), Some(suspended_at)) = (&mut self.start, self.suspended_at.take()) {
+            *start += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        for child in &mut self.children {
+            child.resume(memory);
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|child| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|child| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::timed_parallel::TimedParallel;
+    use crate::tests::{MockNode, context_at};
+
+    #[test]
+    fn test_passes_within_budget() {
+        let clock = ManualClock::new();
+        let fast = MockNode::new("fast", vec![NodeResult::Passed]);
+        let slow = MockNode::new("slow", vec![NodeResult::Running, NodeResult::Passed]);
+        let idle = MockNode::new("idle", vec![NodeResult::Running]);
+        let idle_probe = idle.probe();
+        let mut node = TimedParallel::new("team".into(), vec![Box::new(fast), Box::new(slow), Box::new(idle)], 2, Duration::from_secs(5))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(4));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(idle_probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_deadline_fails_and_halts() {
+        let clock = ManualClock::new();
+        let fast = MockNode::new("fast", vec![NodeResult::Passed]);
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let slow_probe = slow.probe();
+        let mut node = TimedParallel::new("team".into(), vec![Box::new(fast), Box::new(slow)], 2, Duration::from_secs(5))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(3));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(slow_probe.ticks(), 2);
+        assert_eq!(slow_probe.resets(), 1);
+        assert!(node.start.is_none());
+    }
+
+    #[test]
+    fn test_unreachable_requirement_fails_early() {
+        let failing = MockNode::new("failing", vec![NodeResult::Failed]);
+        let running = MockNode::new("running", vec![NodeResult::Running]);
+        let mut node = TimedParallel::new("team".into(), vec![Box::new(failing), Box::new(running)], 2, Duration::from_secs(5))
+            .with_clock(Arc::new(ManualClock::new()));
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let mut node = TimedParallel::new("timed".into(), vec![Box::new(slow)], 1, Duration::from_secs(2))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Failed);
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Gives its child `limit` to finish, measured from the first tick. When the
+/// child is still running once the limit has passed it is reset and
+/// `timeout_result` is returned, `Failed` unless configured otherwise. The
+/// timer is cleared whenever the child finishes.
+pub struct Timeout {
+    pub child: Box<dyn Node>,
+    pub limit: Duration,
+    pub timeout_result: NodeResult,
+    pub start: Option<Instant>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Timeout {
+    pub fn new(name: String, limit: Duration, child: Box<dyn Node>) -> Self {
+        Self::with_result(name, limit, NodeResult::Failed, child)
+    }
+
+    pub fn with_result(name: String, limit: Duration, timeout_result: NodeResult, child: Box<dyn Node>) -> Self {
+        Self {
+            child,
+            limit,
+            timeout_result,
+            start: None,
+            suspended_at: None,
+            clock: Arc::new(SystemClock),
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("Timeout")?;
+        Ok(Self::with_result(config.name.clone(), config.duration("limit")?, config.result("timeout_result")?, child))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let start = *self.start.get_or_insert(now);
+        if now.duration_since(start) >= self.limit {
+            self.start = None;
+            self.child.reset();
+            return self.timeout_result.clone();
+        }
+
+        let result = tick_child_with_error(self.child.as_mut(), memory, &mut trace, &mut self.error);
+        if result != NodeResult::Running {
+            self.start = None;
+        }
+        result
+    }
+}
+
+impl Node for Timeout {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Timeout".into(), self.name.clone())
+            .with("limit", ConfigValue::Duration(self.limit))
+            .with("timeout_result", ConfigValue::Result(self.timeout_result.clone()))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.start = None;
+        self.suspended_at = None;
+        self.child.reset();
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+        self.child.suspend(memory);
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some(start), Some(suspended_at)) = (&mut self.start, self.suspended_at.take()) {
+            *start += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+        self.child.resume(memory);
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::timeout::Timeout;
+    use crate::tests::{MockNode, context_at};
+
+    #[test]
+    fn test_child_finishes_in_time() {
+        let clock = ManualClock::new();
+        let plan = MockNode::new("plan", vec![NodeResult::Running, NodeResult::Passed]);
+        let mut node = Timeout::new("limit".into(), Duration::from_secs(2), Box::new(plan))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert!(node.start.is_none());
+    }
+
+    #[test]
+    fn test_timeout_fires() {
+        let clock = ManualClock::new();
+        let plan = MockNode::new("plan", vec![NodeResult::Running]);
+        let probe = plan.probe();
+        let mut node = Timeout::new("limit".into(), Duration::from_secs(2), Box::new(plan))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.ticks(), 1);
+        assert_eq!(probe.resets(), 1);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_custom_timeout_result() {
+        let clock = ManualClock::new();
+        let plan = MockNode::new("plan", vec![NodeResult::Running]);
+        let mut node = Timeout::with_result("limit".into(), Duration::from_secs(1), NodeResult::Passed, Box::new(plan))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let clock = ManualClock::new();
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let mut node = Timeout::new("limit".into(), Duration::from_secs(2), Box::new(slow))
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 1)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &context_at(&clock, 2)), NodeResult::Failed);
+    }
+}
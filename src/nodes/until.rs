@@ -0,0 +1,175 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Keeps ticking its child until it fails, then returns `Passed`. A child
+/// that passes is reset and started again on the next tick, so the loop
+/// runs at most once per tick instead of spinning.
+pub struct UntilFail {
+    pub child: Box<dyn Node>,
+    pub id: NodeId,
+    pub name: String
+}
+
+/// Keeps ticking its child until it passes, then returns `Passed`. A child
+/// that fails is reset and started again on the next tick.
+pub struct UntilSuccess {
+    pub child: Box<dyn Node>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl UntilFail {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, id: NodeId::next(), name }
+    }
+}
+
+impl UntilSuccess {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, id: NodeId::next(), name }
+    }
+}
+
+fn tick_until(child: &mut Box<dyn Node>, memory: &mut BlackBoard, mut trace: Option<Trace>, until: NodeResult) -> NodeResult {
+    match tick_child(child.as_mut(), memory, &mut trace) {
+        NodeResult::Running => NodeResult::Running,
+        result if result == until => {
+            child.reset();
+            NodeResult::Passed
+        },
+        _ => {
+            child.reset();
+            NodeResult::Running
+        }
+    }
+}
+
+impl Node for UntilFail {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        tick_until(&mut self.child, memory, None, NodeResult::Failed)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = tick_until(&mut self.child, memory, Some(&mut *trace), NodeResult::Failed);
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+impl Node for UntilSuccess {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        tick_until(&mut self.child, memory, None, NodeResult::Passed)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = tick_until(&mut self.child, memory, Some(&mut *trace), NodeResult::Passed);
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::until::{UntilFail, UntilSuccess};
+
+    fn third_tick(memory: &mut BlackBoard, before: NodeResult, on_third: NodeResult) -> NodeResult {
+        let ticks = memory.get::<u32>("ticks").copied().unwrap_or(0) + 1;
+        memory.set("ticks", ticks);
+        match ticks {
+            3 => on_third,
+            _ => before
+        }
+    }
+
+    #[test]
+    fn test_until_fail() {
+        let mut node = UntilFail::new("patrol".into(), Box::new(Action::new("step".into(), |bb| {
+            third_tick(bb, NodeResult::Passed, NodeResult::Failed)
+        })));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<u32>("ticks"), Some(&3));
+    }
+
+    #[test]
+    fn test_until_success() {
+        let mut node = UntilSuccess::new("unlock".into(), Box::new(Action::new("try_key".into(), |bb| {
+            third_tick(bb, NodeResult::Failed, NodeResult::Passed)
+        })));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_running_child_is_propagated() {
+        let mut node = UntilFail::new("wait".into(), Box::new(Action::new("busy".into(), |bb| {
+            third_tick(bb, NodeResult::Running, NodeResult::Failed)
+        })));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(memory.get::<u32>("ticks"), Some(&1));
+        assert_eq!(node.running_child().unwrap().get_name(), "busy");
+    }
+}
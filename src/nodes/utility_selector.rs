@@ -0,0 +1,161 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+pub type ScoreFn = Box<dyn Fn(&BlackBoard) -> f64 + Send + Sync>;
+
+/// A utility arbiter: every tick it scores all children and ticks only the
+/// best one, returning its result. Unlike `PrioritySelector` it commits to
+/// that child instead of falling through to the next one when it fails.
+/// Ties go to the earliest child and NaN scores never win unless every
+/// score is NaN. When the best child changes, the one that was running
+/// before is reset.
+pub struct UtilitySelector {
+    pub children: Vec<(ScoreFn, Box<dyn Node>)>,
+    pub current_child: Option<usize>,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl UtilitySelector {
+    pub fn new(name: String, children: Vec<(ScoreFn, Box<dyn Node>)>) -> Self {
+        Self { children, current_child: None, error: None, id: NodeId::next(), name }
+    }
+
+    fn best(&self, memory: &BlackBoard) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (index, (score, _)) in self.children.iter().enumerate() {
+            let score = score(memory);
+            match best {
+                Some((_, top)) if !(score > top || top.is_nan() && !score.is_nan()) => {},
+                _ => best = Some((index, score))
+            }
+        }
+        best.map(|(index, _)| index)
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        let best = self.best(memory);
+        if let Some(previous) = self.current_child
+            && best != Some(previous) {
+            self.children[previous].1.reset();
+        }
+
+        let Some(index) = best else {
+            self.current_child = None;
+            return NodeResult::Failed;
+        };
+        let result = tick_child_with_error(self.children[index].1.as_mut(), memory, &mut trace, &mut self.error);
+        self.current_child = match result {
+            NodeResult::Running => Some(index),
+            _ => None
+        };
+        result
+    }
+}
+
+impl Node for UtilitySelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for (_, child) in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].1.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|(_, child)| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|(_, child)| child.as_mut() as &mut dyn Node).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::utility_selector::{ScoreFn, UtilitySelector};
+
+    fn value(memory: &BlackBoard, key: &str) -> f64 {
+        memory.get::<f64>(key).copied().unwrap_or(0.0)
+    }
+
+    fn pick(memory: &mut BlackBoard, branch: &'static str, result: NodeResult) -> NodeResult {
+        memory.set("branch", branch);
+        result
+    }
+
+    fn selector() -> UtilitySelector {
+        let eat: ScoreFn = Box::new(|bb| value(bb, "hunger"));
+        let sleep: ScoreFn = Box::new(|bb| value(bb, "fatigue"));
+        let idle: ScoreFn = Box::new(|_| 0.2);
+        UtilitySelector::new("needs".into(), vec![
+            (eat, Box::new(Action::new("eat".into(), |bb| pick(bb, "eat", NodeResult::Failed)))),
+            (sleep, Box::new(Action::new("sleep".into(), |bb| pick(bb, "sleep", NodeResult::Passed)))),
+            (idle, Box::new(Action::new("idle".into(), |bb| pick(bb, "idle", NodeResult::Passed)))),
+        ])
+    }
+
+    #[test]
+    fn test_highest_score_wins() {
+        let mut node = selector();
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"idle"));
+
+        memory.set("fatigue", 0.7_f64);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"sleep"));
+
+        memory.set("hunger", 0.9_f64);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"eat"));
+    }
+
+    #[test]
+    fn test_ties_go_to_first_child() {
+        let mut node = selector();
+        let mut memory = BlackBoard::new();
+        memory.set("hunger", 0.5_f64);
+        memory.set("fatigue", 0.5_f64);
+        node.tick(&mut memory);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"eat"));
+
+        memory.set("hunger", f64::NAN);
+        node.tick(&mut memory);
+        assert_eq!(memory.get::<&str>("branch"), Some(&"sleep"));
+    }
+}
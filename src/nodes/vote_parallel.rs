@@ -0,0 +1,147 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+
+/// Ticks every unfinished child each tick and adds up the weights of the
+/// children that passed. It passes once that sum reaches `threshold` and
+/// fails as soon as the passed weight plus the weight of the children still
+/// running can no longer reach it. Children still running when it finishes
+/// are halted through `reset`.
+pub struct VoteParallel {
+    pub children: Vec<(f64, Box<dyn Node>)>,
+    pub states: Vec<NodeResult>,
+    pub threshold: f64,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl VoteParallel {
+    pub fn new(name: String, threshold: f64, children: Vec<(f64, Box<dyn Node>)>) -> Self {
+        Self {
+            states: vec![NodeResult::Running; children.len()],
+            children,
+            threshold,
+            error: None,
+            id: NodeId::next(),
+            name
+        }
+    }
+
+    fn weight(&self, state: NodeResult) -> f64 {
+        self.children.iter().zip(&self.states)
+            .filter(|(_, child_state)| **child_state == state)
+            .map(|((weight, _), _)| weight)
+            .sum()
+    }
+
+    fn finish(&mut self, result: NodeResult) -> NodeResult {
+        if result != NodeResult::Failed {
+            self.error = None;
+        }
+        for ((_, child), state) in self.children.iter_mut().zip(&mut self.states) {
+            if *state == NodeResult::Running {
+                child.reset();
+            }
+            *state = NodeResult::Running;
+        }
+        result
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        for ((_, child), state) in self.children.iter_mut().zip(&mut self.states) {
+            if *state == NodeResult::Running {
+                *state = tick_child_with_error(child.as_mut(), memory, &mut trace, &mut self.error);
+            }
+        }
+
+        let passed = self.weight(NodeResult::Passed);
+        if passed >= self.threshold {
+            return self.finish(NodeResult::Passed);
+        }
+        if passed + self.weight(NodeResult::Running) < self.threshold {
+            return self.finish(NodeResult::Failed);
+        }
+        NodeResult::Running
+    }
+}
+
+impl Node for VoteParallel {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        for ((_, child), state) in self.children.iter_mut().zip(&mut self.states) {
+            child.reset();
+            *state = NodeResult::Running;
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|(_, child)| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|(_, child)| child.as_mut()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::vote_parallel::VoteParallel;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_heavy_child_meets_threshold() {
+        let lidar = MockNode::new("lidar", vec![NodeResult::Running, NodeResult::Passed]);
+        let camera = MockNode::new("camera", vec![NodeResult::Running]);
+        let sonar = MockNode::new("sonar", vec![NodeResult::Failed]);
+        let camera_probe = camera.probe();
+        let mut node = VoteParallel::new("obstacle".into(), 3.0, vec![
+            (3.0, Box::new(lidar)),
+            (1.0, Box::new(camera)),
+            (1.0, Box::new(sonar)),
+        ]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(camera_probe.resets(), 1);
+    }
+
+    #[test]
+    fn test_light_children_cannot_outvote() {
+        let mut node = VoteParallel::new("obstacle".into(), 3.0, vec![
+            (3.0, Box::new(MockNode::new("lidar", vec![NodeResult::Running, NodeResult::Failed]))),
+            (1.0, Box::new(MockNode::new("camera", vec![NodeResult::Passed]))),
+            (1.0, Box::new(MockNode::new("sonar", vec![NodeResult::Passed]))),
+        ]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Failed);
+    }
+}
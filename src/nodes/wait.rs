@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock, TickContext};
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult};
+
+/// Returns `Running` until `duration` has passed since its first tick, then
+/// `Passed`. `reset` clears the start so the node can be reused.
+pub struct Wait {
+    pub duration: Duration,
+    pub start: Option<Instant>,
+    pub suspended_at: Option<Instant>,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Wait {
+    pub fn new(name: String, duration: Duration) -> Self {
+        Self { duration, start: None, suspended_at: None, clock: Arc::new(SystemClock), id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig) -> Result<Self, String> {
+        config.expect_kind("Wait")?;
+        Ok(Self::new(config.name.clone(), config.duration("duration")?))
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl Node for Wait {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        let now = TickContext::now_or(memory, self.clock.as_ref());
+        let start = *self.start.get_or_insert(now);
+        if now.duration_since(start) >= self.duration {
+            self.start = None;
+            return NodeResult::Passed;
+        }
+        NodeResult::Running
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Wait".into(), self.name.clone())
+            .with("duration", ConfigValue::Duration(self.duration))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.start = None;
+        self.suspended_at = None;
+    }
+
+    fn suspend(&mut self, memory: &BlackBoard) {
+        self.suspended_at = Some(TickContext::now_or(memory, self.clock.as_ref()));
+    }
+
+    fn resume(&mut self, memory: &BlackBoard) {
+        if let (Some(start), Some(suspended_at)) = (&mut self.start, self.suspended_at.take()) {
+            *start += TickContext::now_or(memory, self.clock.as_ref()).duration_since(suspended_at);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::{ManualClock, TickContext};
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::sequence::Sequence;
+    use crate::nodes::wait::Wait;
+
+    #[test]
+    fn test_passes_after_sleeping() {
+        let mut node = Wait::new("pause".into(), Duration::from_millis(20));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        thread::sleep(Duration::from_millis(25));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_reset_restarts_the_wait() {
+        let clock = ManualClock::new();
+        let mut node = Wait::new("pause".into(), Duration::from_secs(2)).with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+    }
+
+    #[test]
+    fn test_context_time_overrides_clock() {
+        let mut node = Wait::new("pause".into(), Duration::from_secs(2));
+        let mut memory = BlackBoard::new();
+        let start = Instant::now();
+        let dt = Duration::from_secs(1);
+
+        assert_eq!(node.tick_ctx(&mut memory, &TickContext::new(start, dt)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &TickContext::new(start + dt, dt)), NodeResult::Running);
+        assert_eq!(node.tick_ctx(&mut memory, &TickContext::new(start + dt * 2, dt)), NodeResult::Passed);
+        assert!(TickContext::current(&memory).is_none());
+        assert!(memory.is_empty());
+        assert_eq!(memory.version(), 0);
+    }
+
+    #[test]
+    fn test_context_reaches_nested_waits() {
+        let mut node = Sequence::new("pauses".into(), vec![
+            Box::new(Wait::new("first".into(), Duration::from_secs(1))),
+            Box::new(Wait::new("second".into(), Duration::from_secs(1))),
+        ]);
+        let mut memory = BlackBoard::new();
+        let start = Instant::now() + Duration::from_secs(3600);
+        let dt = Duration::from_millis(600);
+
+        let results: Vec<NodeResult> = (0..5)
+            .map(|step| node.tick_ctx(&mut memory, &TickContext::new(start + dt * step, dt)))
+            .collect();
+        let mut expected = vec![NodeResult::Running; 4];
+        expected.push(NodeResult::Passed);
+        assert_eq!(results, expected);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child_with_error};
+use crate::rng::Rng;
+
+/// Picks one child with probability proportional to its weight, ticks it and
+/// returns its result, e.g. 70% attack and 30% taunt. A running child keeps
+/// being ticked until it finishes; the next evaluation picks again.
+///
+/// Children with a zero, negative or NaN weight are never picked, unless
+/// no child has a positive weight, in which case all are equally likely.
+pub struct WeightedRandomSelector {
+    pub children: Vec<(f64, Box<dyn Node>)>,
+    pub current_child: Option<usize>,
+    pub rng: Rng,
+    pub error: Option<String>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl WeightedRandomSelector {
+    pub fn new(name: String, children: Vec<(f64, Box<dyn Node>)>, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => Rng::new(seed),
+            None => Rng::from_entropy()
+        };
+        Self { children, current_child: None, rng, error: None, id: NodeId::next(), name }
+    }
+
+    fn pick(&mut self) -> usize {
+        let weight = |weight: f64| if weight > 0.0 { weight } else { 0.0 };
+        let total: f64 = self.children.iter().map(|(w, _)| weight(*w)).sum();
+        if total <= 0.0 {
+            return self.rng.below(self.children.len());
+        }
+
+        let mut target = self.rng.range_f64(0.0, total);
+        let mut last = 0;
+        for (index, (w, _)) in self.children.iter().enumerate() {
+            let w = weight(*w);
+            if w <= 0.0 {
+                continue;
+            }
+            if target < w {
+                return index;
+            }
+            target -= w;
+            last = index;
+        }
+        last
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        self.error = None;
+        if self.children.is_empty() {
+            return NodeResult::Failed;
+        }
+        let index = match self.current_child.take() {
+            Some(index) => index,
+            None => self.pick()
+        };
+        let child = &mut self.children[index].1;
+        let result = tick_child_with_error(child.as_mut(), memory, &mut trace, &mut self.error);
+        match result {
+            NodeResult::Running => self.current_child = Some(index),
+            _ => child.reset()
+        }
+        result
+    }
+}
+
+impl Node for WeightedRandomSelector {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+
+    fn reset(&mut self) {
+        self.error = None;
+        self.current_child = None;
+
+        for (_, child) in &mut self.children {
+            child.reset();
+        }
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        self.current_child.map(|index| self.children[index].1.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        self.children.iter().map(|(_, child)| child.as_ref()).collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        self.children.iter_mut().map(|(_, child)| child.as_mut() as &mut dyn Node).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::weighted_random_selector::WeightedRandomSelector;
+
+    fn count(memory: &mut BlackBoard, index: usize) -> NodeResult {
+        if !memory.contains_key("picks") {
+            memory.set("picks", [0_usize; 3]);
+        }
+        memory.get_mut::<[usize; 3]>("picks").unwrap()[index] += 1;
+        NodeResult::Passed
+    }
+
+    fn frequencies(weights: [f64; 3], iterations: usize) -> [f64; 3] {
+        let mut node = WeightedRandomSelector::new("choice".into(), vec![
+            (weights[0], Box::new(Action::new("a".into(), |bb| count(bb, 0)))),
+            (weights[1], Box::new(Action::new("b".into(), |bb| count(bb, 1)))),
+            (weights[2], Box::new(Action::new("c".into(), |bb| count(bb, 2)))),
+        ], Some(42));
+        let mut memory = BlackBoard::new();
+        for _ in 0..iterations {
+            assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        }
+        memory.get::<[usize; 3]>("picks").unwrap().map(|picks| picks as f64 / iterations as f64)
+    }
+
+    fn assert_close(actual: [f64; 3], expected: [f64; 3]) {
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!((actual - expected).abs() < 0.03, "{actual} is not close to {expected}");
+        }
+    }
+
+    #[test]
+    fn test_frequencies_follow_weights() {
+        assert_close(frequencies([7.0, 3.0, 0.0], 10_000), [0.7, 0.3, 0.0]);
+        assert_close(frequencies([1.0, 1.0, 2.0], 10_000), [0.25, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_all_zero_weights_are_uniform() {
+        let third = 1.0 / 3.0;
+        assert_close(frequencies([0.0, 0.0, 0.0], 10_000), [third, third, third]);
+    }
+
+    #[test]
+    fn test_running_child_is_resumed() {
+        let mut node = WeightedRandomSelector::new("choice".into(), vec![
+            (1.0, Box::new(Action::new("wait".into(), |_| NodeResult::Running))),
+            (1.0, Box::new(Action::new("wander".into(), |_| NodeResult::Running))),
+        ], Some(1));
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        let chosen = node.running_child().unwrap().get_name();
+        for _ in 0..10 {
+            node.tick(&mut memory);
+            assert_eq!(node.running_child().unwrap().get_name(), chosen);
+        }
+    }
+}
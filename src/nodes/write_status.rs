@@ -0,0 +1,123 @@
+use crate::blackboard::BlackBoard;
+use crate::config::{ConfigValue, NodeConfig};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+
+/// Stores its child's `NodeResult` under `key` after every tick and passes
+/// the result through unchanged, so other parts of the tree can read the
+/// outcome of a subtree.
+pub struct WriteStatus {
+    pub child: Box<dyn Node>,
+    pub key: String,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl WriteStatus {
+    pub fn new(name: String, key: String, child: Box<dyn Node>) -> Self {
+        Self { child, key, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig, child: Box<dyn Node>) -> Result<Self, String> {
+        config.expect_kind("WriteStatus")?;
+        Ok(Self::new(config.name.clone(), config.text("key")?, child))
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        memory.set(&self.key, result.clone());
+        result
+    }
+}
+
+impl Node for WriteStatus {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("WriteStatus".into(), self.name.clone())
+            .with("key", ConfigValue::Text(self.key.clone()))
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::write_status::WriteStatus;
+
+    fn step(memory: &mut BlackBoard) -> NodeResult {
+        let step = memory.data.get("step").and_then(|value| value.downcast_ref::<usize>()).copied().unwrap_or(0);
+        memory.data.insert("step".into(), Box::new(step + 1));
+        match step {
+            0 => NodeResult::Running,
+            1 => NodeResult::Passed,
+            _ => NodeResult::Failed
+        }
+    }
+
+    fn status(memory: &BlackBoard) -> NodeResult {
+        memory.data.get("status").unwrap().downcast_ref::<NodeResult>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_status_tracks_child() {
+        let mut node = WriteStatus::new("report".into(), "status".into(), Box::new(Action::new("step".into(), step)));
+        let mut memory = BlackBoard::new();
+        assert!(!memory.contains_key("status"));
+
+        for expected in [NodeResult::Running, NodeResult::Passed, NodeResult::Failed] {
+            assert_eq!(node.tick(&mut memory), expected);
+            assert_eq!(status(&memory), expected);
+        }
+    }
+
+    #[test]
+    fn test_status_write_is_observed() {
+        let mut node = WriteStatus::new("report".into(), "status".into(), Box::new(Action::new("step".into(), step)));
+        let mut memory = BlackBoard::new();
+
+        node.tick(&mut memory);
+        let version = memory.key_version("status");
+        assert!(version > 0);
+        node.tick(&mut memory);
+        assert!(memory.key_version("status") > version);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::blackboard::BlackBoard;
+use crate::config::NodeConfig;
+use crate::node::{Node, NodeId, NodeResult};
+
+/// Returns `Running` once and `Passed` on the following tick, then starts
+/// over. Put it between steps of a sequence to split long work across
+/// frames instead of finishing it all in one tick.
+pub struct Yield {
+    pub yielded: bool,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Yield {
+    pub fn new(name: String) -> Self {
+        Self { yielded: false, id: NodeId::next(), name }
+    }
+
+    pub fn from_config(config: &NodeConfig) -> Result<Self, String> {
+        config.expect_kind("Yield")?;
+        Ok(Self::new(config.name.clone()))
+    }
+}
+
+impl Node for Yield {
+    fn tick(&mut self, _memory: &mut BlackBoard) -> NodeResult {
+        self.yielded = !self.yielded;
+        match self.yielded {
+            true => NodeResult::Running,
+            false => NodeResult::Passed
+        }
+    }
+
+    fn to_config(&self) -> NodeConfig {
+        NodeConfig::new("Yield".into(), self.name.clone())
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn reset(&mut self) {
+        self.yielded = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::sequence::Sequence;
+    use crate::nodes::yielder::Yield;
+    use crate::tests::MockNode;
+
+    #[test]
+    fn test_running_then_passed() {
+        let mut node = Yield::new("frame_break".into());
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+
+        node.reset();
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_splits_sequence_across_ticks() {
+        let load = MockNode::new("load", vec![NodeResult::Passed]);
+        let parse = MockNode::new("parse", vec![NodeResult::Passed]);
+        let (load_probe, parse_probe) = (load.probe(), parse.probe());
+        let mut node = Sequence::new("import".into(), vec![
+            Box::new(load),
+            Box::new(Yield::new("frame_break".into())),
+            Box::new(parse),
+        ]);
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(node.tick(&mut memory), NodeResult::Running);
+        assert_eq!((load_probe.ticks(), parse_probe.ticks()), (1, 0));
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!((load_probe.ticks(), parse_probe.ticks()), (1, 1));
+    }
+}
@@ -0,0 +1,159 @@
+//! Saving and restoring blackboards as JSON, e.g. for save games and
+//! simulation checkpoints. Enabled with the `persistence` feature.
+//!
+//! Values are stored as `Box<dyn Any>`, so only types registered in a
+//! `TypeRegistry` can be written out and read back. Each entry is saved as
+//! `{"type": <tag>, "value": <value>}` so the right type is rebuilt on load.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::blackboard::BlackBoard;
+
+type BoxedValue = Box<dyn Any + Send + Sync>;
+
+struct Registration {
+    tag: String,
+    type_id: TypeId,
+    save: fn(&BoxedValue) -> Result<Value, String>,
+    load: fn(Value) -> Result<BoxedValue, String>
+}
+
+/// The types a blackboard may persist, each under a stable tag.
+#[derive(Default)]
+pub struct TypeRegistry {
+    registrations: Vec<Registration>
+}
+
+impl TypeRegistry {
+    pub fn new() -> Self {
+        Self { registrations: Vec::new() }
+    }
+
+    /// Registers `T` under `tag`. Keep tags stable between versions so older
+    /// saves still load.
+    pub fn register<T: Serialize + DeserializeOwned + Send + Sync + 'static>(mut self, tag: &str) -> Self {
+        self.registrations.push(Registration {
+            tag: tag.to_string(),
+            type_id: TypeId::of::<T>(),
+            save: save::<T>,
+            load: load::<T>
+        });
+        self
+    }
+
+    fn by_type(&self, value: &BoxedValue) -> Option<&Registration> {
+        let type_id = value.as_ref().type_id();
+        self.registrations.iter().find(|registration| registration.type_id == type_id)
+    }
+
+    fn by_tag(&self, tag: &str) -> Option<&Registration> {
+        self.registrations.iter().find(|registration| registration.tag == tag)
+    }
+}
+
+fn save<T: Serialize + 'static>(value: &BoxedValue) -> Result<Value, String> {
+    let value = value.downcast_ref::<T>().ok_or("registered type does not match the value")?;
+    serde_json::to_value(value).map_err(|error| error.to_string())
+}
+
+fn load<T: DeserializeOwned + Send + Sync + 'static>(value: Value) -> Result<BoxedValue, String> {
+    let value: T = serde_json::from_value(value).map_err(|error| error.to_string())?;
+    Ok(Box::new(value))
+}
+
+impl BlackBoard {
+    /// Every entry whose type is in `registry`. Entries of other types, such
+    /// as internal bookkeeping of nodes, are left out.
+    pub fn to_json(&self, registry: &TypeRegistry) -> Result<HashMap<String, Value>, String> {
+        let mut entries = HashMap::new();
+        for (key, value) in &self.data {
+            let Some(registration) = registry.by_type(value) else {
+                continue;
+            };
+            let value = (registration.save)(value).map_err(|error| format!("key `{key}`: {error}"))?;
+            entries.insert(key.clone(), json!({ "type": registration.tag, "value": value }));
+        }
+        Ok(entries)
+    }
+
+    /// Sets every entry of `entries`, as written by `to_json`, keeping the
+    /// other keys. Nothing is set unless every entry loads.
+    pub fn load_json(&mut self, entries: &HashMap<String, Value>, registry: &TypeRegistry) -> Result<(), String> {
+        let mut loaded = Vec::with_capacity(entries.len());
+        for (key, entry) in entries {
+            let tag = entry["type"].as_str().ok_or(format!("key `{key}`: missing type tag"))?;
+            let registration = registry.by_tag(tag).ok_or(format!("key `{key}`: unknown type `{tag}`"))?;
+            let value = (registration.load)(entry["value"].clone()).map_err(|error| format!("key `{key}`: {error}"))?;
+            loaded.push((key.clone(), value));
+        }
+        for (key, value) in loaded {
+            self.insert_entry(&key, value, None);
+        }
+        Ok(())
+    }
+
+    pub fn from_json(entries: &HashMap<String, Value>, registry: &TypeRegistry) -> Result<Self, String> {
+        let mut memory = Self::new();
+        memory.load_json(entries, registry)?;
+        Ok(memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::blackboard::BlackBoard;
+    use crate::persistence::TypeRegistry;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Waypoint {
+        x: f64,
+        y: f64,
+        label: String
+    }
+
+    fn registry() -> TypeRegistry {
+        TypeRegistry::new()
+            .register::<Waypoint>("waypoint")
+            .register::<u32>("u32")
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let registry = registry();
+        let waypoint = Waypoint { x: 1.5, y: -2.0, label: "dock".into() };
+        let mut memory = BlackBoard::new();
+        memory.set("goal", waypoint.clone());
+        memory.set("laps", 3_u32);
+        memory.set("handle", std::time::Duration::from_secs(1));
+
+        let saved = memory.to_json(&registry).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved["goal"]["type"], "waypoint");
+
+        let text = serde_json::to_string(&saved).unwrap();
+        memory.clear();
+        memory.load_json(&serde_json::from_str(&text).unwrap(), &registry).unwrap();
+        assert_eq!(memory.get::<Waypoint>("goal"), Some(&waypoint));
+        assert_eq!(memory.get::<u32>("laps"), Some(&3));
+        assert!(!memory.contains_key("handle"));
+    }
+
+    #[test]
+    fn test_unknown_type_fails_without_changes() {
+        let mut saved = BlackBoard::new();
+        saved.set("laps", 3_u32);
+        let saved = saved.to_json(&registry()).unwrap();
+
+        let mut memory = BlackBoard::new();
+        let error = memory.load_json(&saved, &TypeRegistry::new()).unwrap_err();
+        assert!(error.contains("unknown type `u32`"));
+        assert!(memory.data.is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+use std::any::Any;
+
+use crate::blackboard::BlackBoard;
+use crate::node::{Node, NodeResult};
+use crate::nodes::inverter::Inverter;
+use crate::nodes::selector::Selector;
+use crate::nodes::sequence::Sequence;
+
+/// Where control goes after a leaf finishes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Jump {
+    Next(usize),
+    Return(NodeResult)
+}
+
+pub struct Step {
+    pub leaf: Box<dyn Node>,
+    pub on_passed: Jump,
+    pub on_failed: Jump
+}
+
+/// A behaviour tree compiled into a flat list of leaves.
+///
+/// `Sequence`, `Selector` and `Inverter` nodes are dissolved into jump
+/// targets on their leaves, so a tick is a loop over `steps` instead of a
+/// recursive descent through trait objects. Any other node is kept as an
+/// opaque leaf. A leaf returning `Running` ends the tick with `Running`,
+/// and every tick starts again from the first leaf, matching the first
+/// tick of a freshly reset tree whose leaves hold no state of their own.
+/// Unlike the composites, the plan does not resume at a running leaf.
+pub struct ExecutionPlan {
+    pub steps: Vec<Step>,
+    pub entry: Jump
+}
+
+impl ExecutionPlan {
+    pub fn compile(root: Box<dyn Node>) -> Self {
+        let mut steps = Vec::new();
+        let entry = emit(root, Jump::Return(NodeResult::Passed), Jump::Return(NodeResult::Failed), &mut steps);
+
+        // Leaves were emitted last-to-first so that every jump target was
+        // known up front; flip them back into tree order.
+        let last = steps.len().saturating_sub(1);
+        let flip = |jump: Jump| match jump {
+            Jump::Next(index) => Jump::Next(last - index),
+            result => result
+        };
+        steps.reverse();
+        for step in &mut steps {
+            step.on_passed = flip(step.on_passed.clone());
+            step.on_failed = flip(step.on_failed.clone());
+        }
+        Self { steps, entry: flip(entry) }
+    }
+
+    pub fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        let mut jump = self.entry.clone();
+        loop {
+            let index = match jump {
+                Jump::Next(index) => index,
+                Jump::Return(result) => return result
+            };
+            let step = &mut self.steps[index];
+            jump = match step.leaf.tick(memory) {
+                NodeResult::Passed => step.on_passed.clone(),
+                NodeResult::Failed => step.on_failed.clone(),
+                NodeResult::Running => return NodeResult::Running
+            };
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for step in &mut self.steps {
+            step.leaf.reset();
+        }
+    }
+}
+
+fn emit(node: Box<dyn Node>, on_passed: Jump, on_failed: Jump, steps: &mut Vec<Step>) -> Jump {
+    let kind: &dyn Any = node.as_ref();
+    if kind.is::<Sequence>() {
+        let mut next = on_passed;
+        for child in downcast::<Sequence>(node).children.into_iter().rev() {
+            next = emit(child, next, on_failed.clone(), steps);
+        }
+        return next;
+    }
+    if kind.is::<Selector>() {
+        let mut next = on_failed;
+        for child in downcast::<Selector>(node).children.into_iter().rev() {
+            next = emit(child, on_passed.clone(), next, steps);
+        }
+        return next;
+    }
+    if kind.is::<Inverter>() {
+        return emit(downcast::<Inverter>(node).child, on_failed, on_passed, steps);
+    }
+
+    steps.push(Step { leaf: node, on_passed, on_failed });
+    Jump::Next(steps.len() - 1)
+}
+
+fn downcast<T: Node>(node: Box<dyn Node>) -> Box<T> {
+    let node: Box<dyn Any> = node;
+    node.downcast::<T>().expect("node kind checked before downcast")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeResult};
+    use crate::nodes::action::Action;
+    use crate::nodes::inverter::Inverter;
+    use crate::nodes::selector::Selector;
+    use crate::nodes::sequence::Sequence;
+    use crate::plan::ExecutionPlan;
+
+    fn record(memory: &mut BlackBoard, code: u8) {
+        let mut trace = memory.data.remove("trace")
+            .and_then(|value| value.downcast::<Vec<u8>>().ok())
+            .map(|trace| *trace)
+            .unwrap_or_default();
+        trace.push(code);
+        memory.data.insert("trace".into(), Box::new(trace));
+    }
+
+    fn flag(memory: &BlackBoard, key: &str) -> bool {
+        memory.data.get(key).and_then(|value| value.downcast_ref::<bool>()).copied().unwrap_or(false)
+    }
+
+    const LEAVES: [fn(&mut BlackBoard) -> NodeResult; 5] = [
+        |bb| { record(bb, 0); NodeResult::Passed },
+        |bb| { record(bb, 1); NodeResult::Failed },
+        |bb| { record(bb, 2); NodeResult::Running },
+        |bb| { record(bb, 3); if flag(bb, "a") { NodeResult::Passed } else { NodeResult::Failed } },
+        |bb| { record(bb, 4); if flag(bb, "b") { NodeResult::Passed } else { NodeResult::Running } },
+    ];
+
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % bound as u64) as usize
+        }
+    }
+
+    fn random_tree(rng: &mut Lcg, depth: usize) -> Box<dyn Node> {
+        let kind = if depth == 0 { 3 } else { rng.next(4) };
+        match kind {
+            0 => Box::new(Sequence::new("sequence".into(), (0..rng.next(4)).map(|_| random_tree(rng, depth - 1)).collect())),
+            1 => Box::new(Selector::new("selector".into(), (0..rng.next(4)).map(|_| random_tree(rng, depth - 1)).collect())),
+            2 => Box::new(Inverter::new(random_tree(rng, depth - 1), "inverter".into())),
+            _ => Box::new(Action::new("leaf".into(), LEAVES[rng.next(LEAVES.len())]))
+        }
+    }
+
+    fn run(node: &mut dyn FnMut(&mut BlackBoard) -> NodeResult, a: bool, b: bool) -> (NodeResult, Vec<u8>) {
+        let mut memory = BlackBoard::new();
+        memory.data.insert("a".into(), Box::new(a));
+        memory.data.insert("b".into(), Box::new(b));
+        let result = node(&mut memory);
+        let trace = memory.data.remove("trace")
+            .and_then(|value| value.downcast::<Vec<u8>>().ok())
+            .map(|trace| *trace)
+            .unwrap_or_default();
+        (result, trace)
+    }
+
+    #[test]
+    fn test_plan_matches_recursive_tick() {
+        for seed in 0..200 {
+            let mut tree = random_tree(&mut Lcg(seed), 4);
+            let mut plan = ExecutionPlan::compile(random_tree(&mut Lcg(seed), 4));
+            for (a, b) in [(false, false), (true, false), (false, true), (true, true)] {
+                tree.reset();
+                assert_eq!(
+                    run(&mut |bb| plan.tick(bb), a, b),
+                    run(&mut |bb| tree.tick(bb), a, b),
+                    "seed {seed}, a {a}, b {b}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_composites() {
+        let mut memory = BlackBoard::new();
+        let mut plan = ExecutionPlan::compile(Box::new(Sequence::new("empty".into(), vec![])));
+        assert_eq!(plan.tick(&mut memory), NodeResult::Passed);
+
+        let mut plan = ExecutionPlan::compile(Box::new(Inverter::new(Box::new(Selector::new("empty".into(), vec![])), "not".into())));
+        assert!(plan.steps.is_empty());
+        assert_eq!(plan.tick(&mut memory), NodeResult::Passed);
+    }
+}
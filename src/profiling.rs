@@ -0,0 +1,326 @@
+//! Per-subtree tick timing and tick traces. Enabled with the `profiling` feature.
+
+use std::any::Any;
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::blackboard::BlackBoard;
+use crate::clock::{Clock, SystemClock};
+use crate::node::{Node, NodeId, NodeResult, Trace, tick_child};
+use crate::tree::BehaviouralTree;
+
+/// Adds up the time spent ticking its child, including everything below it.
+pub struct Profiled {
+    pub child: Box<dyn Node>,
+    pub total: Duration,
+    pub ticks: u64,
+    pub clock: Arc<dyn Clock>,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Profiled {
+    pub fn new(name: String, child: Box<dyn Node>) -> Self {
+        Self { child, total: Duration::ZERO, ticks: 0, clock: Arc::new(SystemClock), id: NodeId::next(), name }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let start = self.clock.now();
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        self.total += self.clock.now().duration_since(start);
+        self.ticks += 1;
+        result
+    }
+}
+
+impl Node for Profiled {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+impl BehaviouralTree {
+    /// The `n` subtrees with the most cumulative tick time, slowest first,
+    /// as `(child name, total)` for every `Profiled` node in the tree.
+    pub fn slowest(&self, n: usize) -> Vec<(String, Duration)> {
+        let mut timings = Vec::new();
+        let mut pending = vec![self.root.as_ref()];
+        while let Some(node) = pending.pop() {
+            let any: &dyn Any = node;
+            if let Some(profiled) = any.downcast_ref::<Profiled>() {
+                timings.push((profiled.child.get_name(), profiled.total));
+            }
+            pending.extend(node.children());
+        }
+        timings.sort_by_key(|(_, total)| Reverse(*total));
+        timings.truncate(n);
+        timings
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    pub name: String,
+    pub kind: String,
+    pub begin: bool,
+    pub at: Instant
+}
+
+/// Collects begin/end events from `Traced` nodes. Clones share the same
+/// events, so one handle can export while others sit in the tree.
+#[derive(Clone)]
+pub struct Tracer {
+    pub start: Instant,
+    events: Arc<Mutex<Vec<TraceEvent>>>,
+    clock: Arc<dyn Clock>
+}
+
+impl Tracer {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self { start: clock.now(), events: Arc::new(Mutex::new(Vec::new())), clock }
+    }
+
+    pub fn record(&self, name: String, kind: String, begin: bool) {
+        let at = self.clock.now();
+        self.events.lock().unwrap().push(TraceEvent { name, kind, begin, at });
+    }
+
+    pub fn events(&self) -> Vec<TraceEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// The events in the Chrome trace event format, loadable in
+    /// `chrome://tracing` or Perfetto. Timestamps are microseconds since
+    /// the tracer was created.
+    pub fn export_chrome_trace(&self) -> String {
+        let mut json = String::from("{\"traceEvents\":[");
+        for (index, event) in self.events.lock().unwrap().iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"{}\",\"ts\":{},\"pid\":1,\"tid\":1}}",
+                escape(&event.name),
+                escape(&event.kind),
+                if event.begin { "B" } else { "E" },
+                event.at.duration_since(self.start).as_micros()
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", character as u32);
+            },
+            character => escaped.push(character)
+        }
+    }
+    escaped
+}
+
+/// Records a begin and an end event in `tracer` around every tick of its
+/// child, under the child's name and kind.
+pub struct Traced {
+    pub child: Box<dyn Node>,
+    pub tracer: Tracer,
+    pub id: NodeId,
+    pub name: String
+}
+
+impl Traced {
+    pub fn new(name: String, tracer: Tracer, child: Box<dyn Node>) -> Self {
+        Self { child, tracer, id: NodeId::next(), name }
+    }
+
+    fn run(&mut self, memory: &mut BlackBoard, mut trace: Option<Trace>) -> NodeResult {
+        let (name, kind) = (self.child.get_name(), self.child.kind());
+        self.tracer.record(name.clone(), kind.clone(), true);
+        let result = tick_child(self.child.as_mut(), memory, &mut trace);
+        self.tracer.record(name, kind, false);
+        result
+    }
+}
+
+impl Node for Traced {
+    fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+        self.run(memory, None)
+    }
+
+    fn tick_traced(&mut self, memory: &mut BlackBoard, trace: Trace) -> NodeResult {
+        let id = self.id();
+        trace.enter(id, &self.name);
+        let result = self.run(memory, Some(&mut *trace));
+        trace.exit(id, &self.name, &result);
+        result
+    }
+
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn last_error(&self) -> Option<String> {
+        self.child.last_error()
+    }
+
+    fn reset(&mut self) {
+        self.child.reset();
+    }
+
+    fn running_child(&self) -> Option<&dyn Node> {
+        Some(self.child.as_ref())
+    }
+
+    fn children(&self) -> Vec<&dyn Node> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Node> {
+        vec![self.child.as_mut()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::clock::ManualClock;
+
+    use crate::blackboard::BlackBoard;
+    use crate::node::{Node, NodeId, NodeResult};
+    use crate::nodes::sequence::Sequence;
+    use crate::profiling::{Profiled, Traced, Tracer};
+    use crate::tree::BehaviouralTree;
+
+    fn profiled(clock: &ManualClock, name: &str, millis: u64) -> Box<dyn Node> {
+        let step = SlowStep { clock: clock.clone(), name: name.into(), step: Duration::from_millis(millis), id: NodeId::next() };
+        Box::new(Profiled::new(format!("{name}_profile"), Box::new(step)).with_clock(Arc::new(clock.clone())))
+    }
+
+    #[test]
+    fn test_slowest_ranking() {
+        let clock = ManualClock::new();
+        let root = Sequence::new("root".into(), vec![
+            profiled(&clock, "fast", 1),
+            profiled(&clock, "slow", 20),
+            profiled(&clock, "medium", 8),
+        ]);
+        let root = Profiled::new("root_profile".into(), Box::new(root)).with_clock(Arc::new(clock.clone()));
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+        tree.tick(&mut memory);
+        tree.tick(&mut memory);
+
+        let slowest = tree.slowest(3);
+        let names: Vec<_> = slowest.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["root", "slow", "medium"]);
+        assert_eq!(slowest[0].1, Duration::from_millis(58));
+        assert_eq!(slowest[1].1, Duration::from_millis(40));
+        assert_eq!(tree.slowest(10).len(), 4);
+    }
+
+    #[test]
+    fn test_chrome_trace_export() {
+        let clock = ManualClock::new();
+        let tracer = Tracer::new(Arc::new(clock.clone()));
+        let walk = SlowStep { clock: clock.clone(), name: "walk \"slow\"".into(), step: Duration::from_micros(250), id: NodeId::next() };
+        let mut root = Traced::new("root_trace".into(), tracer.clone(), Box::new(Sequence::new("root".into(), vec![
+            Box::new(Traced::new("walk_trace".into(), tracer.clone(), Box::new(walk))),
+        ])));
+        clock.advance(Duration::from_micros(10));
+        root.tick(&mut BlackBoard::new());
+
+        let trace: serde_json::Value = serde_json::from_str(&tracer.export_chrome_trace()).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+        let fields: Vec<_> = events.iter()
+            .map(|event| (event["name"].as_str().unwrap(), event["ph"].as_str().unwrap(), event["ts"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(fields, [
+            ("root", "B", 10),
+            ("walk \"slow\"", "B", 10),
+            ("walk \"slow\"", "E", 260),
+            ("root", "E", 260),
+        ]);
+        assert_eq!(events[1]["cat"], "SlowStep");
+    }
+
+    struct SlowStep {
+        clock: ManualClock,
+        name: String,
+        step: Duration,
+        id: NodeId
+    }
+
+    impl Node for SlowStep {
+        fn tick(&mut self, _memory: &mut BlackBoard) -> NodeResult {
+            self.clock.advance(self.step);
+            NodeResult::Passed
+        }
+
+        fn get_name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
+
+        fn reset(&mut self) { }
+    }
+}
@@ -0,0 +1,119 @@
+//! Runtime registration of node types, so data-driven trees and plugins can
+//! use node kinds this crate doesn't know about.
+
+use std::collections::HashMap;
+
+use crate::config::NodeConfig;
+use crate::node::Node;
+
+pub type NodeFactory = Box<dyn Fn(&NodeConfig, Vec<Box<dyn Node>>) -> Result<Box<dyn Node>, String> + Send + Sync>;
+
+/// Maps type names to factories building a node from its config and its
+/// already built children, in the shape of the `from_config` constructors.
+/// Leaf factories should reject children they were given rather than drop
+/// them, e.g. with `no_children`.
+#[derive(Default)]
+pub struct NodeRegistry {
+    factories: HashMap<String, NodeFactory>
+}
+
+impl NodeRegistry {
+    pub fn new() -> Self {
+        Self { factories: HashMap::new() }
+    }
+
+    /// Registers `factory` under `kind`, replacing any earlier one.
+    pub fn register(&mut self, kind: &str, factory: impl Fn(&NodeConfig, Vec<Box<dyn Node>>) -> Result<Box<dyn Node>, String> + Send + Sync + 'static) {
+        self.factories.insert(kind.to_string(), Box::new(factory));
+    }
+
+    pub fn contains(&self, kind: &str) -> bool {
+        self.factories.contains_key(kind)
+    }
+
+    pub fn create(&self, kind: &str, params: &NodeConfig, children: Vec<Box<dyn Node>>) -> Result<Box<dyn Node>, String> {
+        let factory = self.factories.get(kind).ok_or_else(|| format!("no node type registered as '{kind}'"))?;
+        factory(params, children)
+    }
+}
+
+/// Fails unless `children` is empty, for factories of leaf kinds.
+pub fn no_children(config: &NodeConfig, children: &[Box<dyn Node>]) -> Result<(), String> {
+    match children.is_empty() {
+        true => Ok(()),
+        false => Err(format!("{} '{}' does not take children", config.kind, config.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::config::{ConfigValue, NodeConfig};
+    use crate::node::{Node, NodeId, NodeResult};
+    use crate::nodes::parallel::Parallel;
+    use crate::nodes::range_guard::RangeGuard;
+    use crate::registry::{NodeRegistry, no_children};
+    use crate::tests::MockNode;
+
+    struct Beep {
+        times: i64,
+        id: NodeId,
+        name: String
+    }
+
+    impl Node for Beep {
+        fn tick(&mut self, memory: &mut BlackBoard) -> NodeResult {
+            *memory.entry_or_insert_with("beeps", || 0_i64) += self.times;
+            NodeResult::Passed
+        }
+
+        fn reset(&mut self) { }
+
+        fn get_name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn id(&self) -> NodeId {
+            self.id
+        }
+    }
+
+    #[test]
+    fn test_create_registered_type() {
+        let mut registry = NodeRegistry::new();
+        registry.register("beep", |config, children| {
+            no_children(config, &children)?;
+            Ok(Box::new(Beep { times: config.int("times")?, id: NodeId::next(), name: config.name.clone() }))
+        });
+        registry.register("range_guard", |config, _| Ok(Box::new(RangeGuard::from_config(config)?)));
+        assert!(registry.contains("beep"));
+
+        let config = NodeConfig::new("beep".into(), "alarm".into()).with("times", ConfigValue::Int(3));
+        let mut node = registry.create("beep", &config, Vec::new()).unwrap();
+        let mut memory = BlackBoard::new();
+        assert_eq!(node.get_name(), "alarm");
+        assert_eq!(node.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(memory.get::<i64>("beeps"), Some(&3));
+
+        let missing = NodeConfig::new("beep".into(), "alarm".into());
+        assert_eq!(registry.create("beep", &missing, Vec::new()).err().unwrap(), "beep 'alarm' is missing parameter 'times'");
+        assert_eq!(registry.create("siren", &config, Vec::new()).err().unwrap(), "no node type registered as 'siren'");
+        let extra: Vec<Box<dyn Node>> = vec![Box::new(MockNode::new("horn", vec![NodeResult::Passed]))];
+        assert_eq!(registry.create("beep", &config, extra).err().unwrap(), "beep 'alarm' does not take children");
+    }
+
+    #[test]
+    fn test_registered_composite_gets_children() {
+        let mut registry = NodeRegistry::new();
+        registry.register("Parallel", |config, children| Ok(Box::new(Parallel::from_config(config, children)?)));
+        let config = NodeConfig::new("Parallel".into(), "team".into()).with("policy", ConfigValue::Text("require_all".into()));
+        let children: Vec<Box<dyn Node>> = vec![
+            Box::new(MockNode::new("walk", vec![NodeResult::Passed])),
+            Box::new(MockNode::new("talk", vec![NodeResult::Running])),
+        ];
+        let mut node = registry.create("Parallel", &config, children).unwrap();
+
+        assert_eq!(node.children().len(), 2);
+        assert_eq!(node.tick(&mut BlackBoard::new()), NodeResult::Running);
+    }
+}
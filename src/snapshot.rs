@@ -0,0 +1,145 @@
+//! Plain-data view of a tree for debuggers and visualizers. With the
+//! `serde` feature the snapshot types implement `Serialize`/`Deserialize`.
+
+use std::collections::HashMap;
+
+use crate::node::{Node, NodeId, NodeResult};
+use crate::tree::BehaviouralTree;
+
+/// One node of a `TreeSnapshot`. `id` is the node's `Node::id`, the same id
+/// traces and stats report; `children` holds the ids of its direct children.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeSnapshot {
+    pub id: NodeId,
+    pub name: String,
+    pub kind: String,
+    pub status: Option<NodeResult>,
+    pub last_error: Option<String>,
+    pub children: Vec<NodeId>
+}
+
+/// The state of a whole tree after its last tick, nodes in pre-order so the
+/// root comes first. The root's status is the tree's last result; below it
+/// only the nodes on the running path have a status (`Running`), since
+/// nodes don't keep their own last result.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeSnapshot {
+    pub nodes: Vec<NodeSnapshot>,
+    pub tick_rate: Option<f64>
+}
+
+impl TreeSnapshot {
+    pub fn node(&self, id: NodeId) -> Option<&NodeSnapshot> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+}
+
+impl BehaviouralTree {
+    pub fn snapshot(&self) -> TreeSnapshot {
+        let mut nodes = Vec::new();
+        collect(self.root.as_ref(), &mut nodes);
+
+        if let Some(result) = &self.last_result {
+            nodes[0].status = Some(result.clone());
+        }
+        if self.last_result == Some(NodeResult::Running) {
+            let positions: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(index, node)| (node.id, index)).collect();
+            let (mut parent, mut position) = (self.root.as_ref(), 0);
+            // Stops at a running child that is not among its parent's
+            // children, rather than marking a node elsewhere in the tree.
+            while let Some(child) = parent.running_child()
+                && nodes[position].children.contains(&child.id()) {
+                position = positions[&child.id()];
+                nodes[position].status = Some(NodeResult::Running);
+                parent = child;
+            }
+        }
+        TreeSnapshot { nodes, tick_rate: self.tick_rate() }
+    }
+}
+
+fn collect(node: &dyn Node, nodes: &mut Vec<NodeSnapshot>) {
+    let children = node.children();
+    nodes.push(NodeSnapshot {
+        id: node.id(),
+        name: node.get_name(),
+        kind: node.kind(),
+        status: None,
+        last_error: node.last_error(),
+        children: children.iter().map(|child| child.id()).collect()
+    });
+    for child in children {
+        collect(child, nodes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::NodeResult;
+    use crate::nodes::inverter::Inverter;
+    use crate::nodes::sequence::Sequence;
+    use crate::tests::MockNode;
+    use crate::tree::BehaviouralTree;
+
+    fn tree() -> BehaviouralTree {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(Inverter::new(Box::new(MockNode::new("blocked", vec![NodeResult::Failed])), "not_blocked".into())),
+            Box::new(MockNode::new("walk", vec![NodeResult::Running, NodeResult::Passed])),
+        ]);
+        BehaviouralTree::new(Box::new(root), BlackBoard::new())
+    }
+
+    #[test]
+    fn test_snapshot_structure() {
+        let snapshot = tree().snapshot();
+        let names: Vec<_> = snapshot.nodes.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["root", "not_blocked", "blocked", "walk"]);
+        assert_eq!(snapshot.nodes[0].kind, "Sequence");
+        assert_eq!(snapshot.nodes[1].kind, "Inverter");
+        assert_eq!(snapshot.nodes[0].children, [snapshot.nodes[1].id, snapshot.nodes[3].id]);
+        assert_eq!(snapshot.nodes[1].children, [snapshot.nodes[2].id]);
+        assert_eq!(snapshot.node(snapshot.nodes[2].id).unwrap().name, "blocked");
+        assert!(snapshot.nodes.iter().all(|node| node.status.is_none()));
+    }
+
+    #[test]
+    fn test_snapshot_statuses() {
+        let mut tree = tree();
+        let mut memory = BlackBoard::new();
+
+        tree.tick(&mut memory);
+        let statuses: Vec<_> = tree.snapshot().nodes.into_iter().map(|node| node.status).collect();
+        assert_eq!(statuses, [Some(NodeResult::Running), None, None, Some(NodeResult::Running)]);
+
+        tree.tick(&mut memory);
+        let statuses: Vec<_> = tree.snapshot().nodes.into_iter().map(|node| node.status).collect();
+        assert_eq!(statuses, [Some(NodeResult::Passed), None, None, None]);
+    }
+
+    #[test]
+    fn test_snapshot_tells_same_named_nodes_apart() {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(MockNode::new("step", vec![NodeResult::Passed])),
+            Box::new(MockNode::new("step", vec![NodeResult::Running])),
+        ]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        tree.tick(&mut BlackBoard::new());
+
+        let statuses: Vec<_> = tree.snapshot().nodes.into_iter().map(|node| node.status).collect();
+        assert_eq!(statuses, [Some(NodeResult::Running), None, Some(NodeResult::Running)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut tree = tree();
+        tree.tick(&mut BlackBoard::new());
+        let snapshot = tree.snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains("\"kind\":\"Inverter\""));
+        assert_eq!(serde_json::from_str::<crate::snapshot::TreeSnapshot>(&json).unwrap(), snapshot);
+    }
+}
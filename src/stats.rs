@@ -0,0 +1,77 @@
+//! Per-node tick counts and durations, collected by a tree once
+//! `BehaviouralTree::enable_stats` is called. Like tracing, it sees the
+//! nodes reported through `Node::tick_traced`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::clock::Clock;
+use crate::node::{NodeId, NodeResult, TickObserver};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeStats {
+    pub name: String,
+    pub ticks: u64,
+    pub total: Duration,
+    pub last: Duration,
+    pub min: Duration,
+    pub max: Duration
+}
+
+impl NodeStats {
+    pub fn mean(&self) -> Duration {
+        Duration::from_secs_f64(self.total.as_secs_f64() / self.ticks.max(1) as f64)
+    }
+}
+
+pub struct TickStats {
+    pub nodes: HashMap<NodeId, NodeStats>,
+    pub clock: Arc<dyn Clock>,
+    started: Vec<Instant>
+}
+
+impl TickStats {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self { nodes: HashMap::new(), clock, started: Vec::new() }
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&NodeStats> {
+        self.nodes.get(&id)
+    }
+
+    /// The stats of every node called `name`, since names may repeat.
+    pub fn by_name(&self, name: &str) -> Vec<&NodeStats> {
+        self.nodes.values().filter(|stats| stats.name == name).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+}
+
+impl TickObserver for TickStats {
+    fn enter(&mut self, _id: NodeId, _name: &str) {
+        self.started.push(self.clock.now());
+    }
+
+    fn exit(&mut self, id: NodeId, name: &str, _result: &NodeResult) {
+        let Some(start) = self.started.pop() else {
+            return;
+        };
+        let elapsed = self.clock.now().duration_since(start);
+        let stats = self.nodes.entry(id).or_insert_with(|| NodeStats {
+            name: name.to_string(),
+            ticks: 0,
+            total: Duration::ZERO,
+            last: Duration::ZERO,
+            min: Duration::MAX,
+            max: Duration::ZERO
+        });
+        stats.ticks += 1;
+        stats.total += elapsed;
+        stats.last = elapsed;
+        stats.min = stats.min.min(elapsed);
+        stats.max = stats.max.max(elapsed);
+    }
+}
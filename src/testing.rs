@@ -0,0 +1,70 @@
+//! Helpers for testing behaviour trees. Enabled with the `testing` feature.
+
+use crate::node::{NodeResult, running_path};
+use crate::tree::BehaviouralTree;
+
+/// Ticks `tree` against its own blackboard until it returns a result other
+/// than `Running`, at most `max_ticks` times, and asserts that result is
+/// `expected`. Panics with the active node path if the tree is still
+/// running after `max_ticks`.
+pub fn assert_completes_within(tree: &mut BehaviouralTree, max_ticks: usize, expected: NodeResult) {
+    let mut memory = std::mem::take(&mut tree.blackboard);
+    let mut outcome = None;
+    for tick in 1..=max_ticks {
+        let result = tree.tick(&mut memory);
+        if result != NodeResult::Running {
+            outcome = Some((tick, result));
+            break;
+        }
+    }
+    let path = running_path(tree.root.as_ref()).join(" > ");
+    tree.blackboard = memory;
+
+    match outcome {
+        Some((tick, result)) => assert!(
+            result == expected,
+            "tree finished with {result:?} on tick {tick}, expected {expected:?}"
+        ),
+        None => panic!(
+            "tree still running after {max_ticks} ticks, expected {expected:?}; active path: {path}"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blackboard::BlackBoard;
+    use crate::node::NodeResult;
+    use crate::nodes::sequence::Sequence;
+    use crate::testing::assert_completes_within;
+    use crate::tests::MockNode;
+    use crate::tree::BehaviouralTree;
+
+    fn tree(script: Vec<NodeResult>) -> BehaviouralTree {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(MockNode::new("ready", vec![NodeResult::Passed])),
+            Box::new(MockNode::new("work", script)),
+        ]);
+        BehaviouralTree::new(Box::new(root), BlackBoard::new())
+    }
+
+    #[test]
+    fn test_completing_tree() {
+        let mut tree = tree(vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+        assert_completes_within(&mut tree, 3, NodeResult::Passed);
+    }
+
+    #[test]
+    #[should_panic(expected = "tree still running after 5 ticks, expected Passed; active path: root > work")]
+    fn test_stuck_tree() {
+        let mut tree = tree(vec![NodeResult::Running]);
+        assert_completes_within(&mut tree, 5, NodeResult::Passed);
+    }
+
+    #[test]
+    #[should_panic(expected = "tree finished with Failed on tick 2, expected Passed")]
+    fn test_wrong_result() {
+        let mut tree = tree(vec![NodeResult::Running, NodeResult::Failed]);
+        assert_completes_within(&mut tree, 5, NodeResult::Passed);
+    }
+}
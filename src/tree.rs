@@ -1,15 +1,606 @@
-use crate::{blackboard::BlackBoard, node::{Node, NodeResult}};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::{blackboard::BlackBoard, clock::{Clock, SystemClock, TickContext}, node::{Node, NodeId, NodeResult, TickObserver}};
+use crate::stats::TickStats;
+
+/// Default blackboard key checked for a `TreeControl` signal before each tick.
+pub const CONTROL_KEY: &str = "tree_control";
+
+/// Number of recent tick timestamps kept for `BehaviouralTree::tick_rate`.
+pub const TICK_RATE_WINDOW: usize = 32;
+
+/// Signals external systems can push onto the blackboard to steer the tree.
+/// `Pause` holds until it is replaced by `Resume`; `Abort` is consumed by the
+/// tick that handles it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeControl {
+    Pause,
+    Resume,
+    Abort
+}
+
+/// The shape of a tree below some node: its name and kind, then the same for
+/// each child in tick order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeDescription {
+    pub name: String,
+    pub kind: String,
+    pub children: Vec<NodeDescription>
+}
+
+impl NodeDescription {
+    pub fn of(node: &dyn Node) -> Self {
+        Self {
+            name: node.get_name(),
+            kind: node.kind(),
+            children: node.children().into_iter().map(NodeDescription::of).collect()
+        }
+    }
+
+    fn render(&self, depth: usize, out: &mut String) {
+        out.push_str(&format!("{}{} ({})\n", "  ".repeat(depth), self.name, self.kind));
+        for child in &self.children {
+            child.render(depth + 1, out);
+        }
+    }
+}
+
+pub type TraceHook = Box<dyn FnMut(&str, NodeResult) + Send + Sync>;
+
+/// Everything watching a tick: the trace hook and the stats collector.
+struct Observers<'a> {
+    trace: Option<&'a mut TraceHook>,
+    stats: Option<&'a mut TickStats>
+}
+
+impl TickObserver for Observers<'_> {
+    fn enter(&mut self, id: NodeId, name: &str) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.enter(id, name);
+        }
+    }
+
+    fn exit(&mut self, id: NodeId, name: &str, result: &NodeResult) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.exit(id, name, result);
+        }
+        if let Some(trace) = self.trace.as_mut() {
+            trace(name, result.clone());
+        }
+    }
+}
 
 pub struct BehaviouralTree {
     pub root: Box<dyn Node>,
-    pub blackboard: BlackBoard
+    pub blackboard: BlackBoard,
+    pub control_key: String,
+    pub last_result: Option<NodeResult>,
+    pub clock: Arc<dyn Clock>,
+    pub tick_times: VecDeque<Instant>,
+    pub suspended: bool,
+    pub trace: Option<TraceHook>,
+    pub stats: Option<TickStats>,
+    pub ticked_version: Option<u64>
 }
 
 impl BehaviouralTree {
     pub fn new(root: Box<dyn Node>, blackboard: BlackBoard) -> Self {
-        Self { root, blackboard }
+        Self {
+            root,
+            blackboard,
+            control_key: CONTROL_KEY.into(),
+            last_result: None,
+            clock: Arc::new(SystemClock),
+            tick_times: VecDeque::with_capacity(TICK_RATE_WINDOW),
+            suspended: false,
+            trace: None,
+            stats: None,
+            ticked_version: None
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_control_key(mut self, key: String) -> Self {
+        self.control_key = key;
+        self
+    }
+
+    /// Calls `trace` with the name and result of every node ticked from now
+    /// on, in the order they finish, e.g. to log the execution path of each
+    /// tick. See `Node::tick_traced` for which nodes report their children.
+    pub fn set_trace(&mut self, trace: TraceHook) {
+        self.trace = Some(trace);
+    }
+
+    pub fn clear_trace(&mut self) {
+        self.trace = None;
+    }
+
+    /// Starts counting ticks and timing them per node, using the tree's
+    /// clock. Existing stats are kept if already enabled.
+    pub fn enable_stats(&mut self) {
+        if self.stats.is_none() {
+            self.stats = Some(TickStats::new(self.clock.clone()));
+        }
     }
+
+    pub fn disable_stats(&mut self) {
+        self.stats = None;
+    }
+
+    pub fn stats(&self) -> Option<&TickStats> {
+        self.stats.as_ref()
+    }
+
+    /// Resets every node so the next tick starts the tree from scratch, e.g.
+    /// between episodes of a simulation.
+    pub fn reset(&mut self) {
+        self.root.reset();
+        self.last_result = None;
+        self.ticked_version = None;
+    }
+
+    /// Aborts whatever is running, letting the running nodes clean up, see
+    /// `Node::interrupt`. The next tick starts from the root again.
+    pub fn interrupt(&mut self) {
+        self.root.interrupt();
+        self.last_result = None;
+        self.ticked_version = None;
+    }
+
+    pub fn reset_blackboard(&mut self) {
+        self.blackboard.clear();
+    }
+
+    /// Freezes the tree where it is. Until `resume`, `tick` returns the last
+    /// result without ticking the root, and time-based nodes stop counting.
+    /// Unlike `reset`, no progress is discarded.
+    pub fn suspend(&mut self, blackboard: &BlackBoard) {
+        if !self.suspended {
+            self.suspended = true;
+            self.root.suspend(blackboard);
+        }
+    }
+
+    pub fn resume(&mut self, blackboard: &BlackBoard) {
+        if self.suspended {
+            self.suspended = false;
+            self.root.resume(blackboard);
+        }
+    }
+
+    /// Suspends like `suspend` at the time of `context`, for trees ticked
+    /// with `tick_ctx`.
+    pub fn suspend_ctx(&mut self, blackboard: &mut BlackBoard, context: &TickContext) {
+        context.run(blackboard, |blackboard| self.suspend(blackboard));
+    }
+
+    /// Resumes like `resume` at the time of `context`, for trees ticked with
+    /// `tick_ctx`.
+    pub fn resume_ctx(&mut self, blackboard: &mut BlackBoard, context: &TickContext) {
+        context.run(blackboard, |blackboard| self.resume(blackboard));
+    }
+
+    /// Ticks like `tick` with `context` as the time of the tick, see
+    /// `Node::tick_ctx`.
+    pub fn tick_ctx(&mut self, blackboard: &mut BlackBoard, context: &TickContext) -> NodeResult {
+        context.run(blackboard, |blackboard| self.tick(blackboard))
+    }
+
     pub fn tick(&mut self, blackboard: &mut BlackBoard) -> NodeResult {
-        self.root.tick(blackboard)
+        blackboard.remove_expired();
+        if self.suspended {
+            return self.last_result.clone().unwrap_or(NodeResult::Running);
+        }
+        if self.tick_times.len() == TICK_RATE_WINDOW {
+            self.tick_times.pop_front();
+        }
+        self.tick_times.push_back(self.clock.now());
+
+        let control = blackboard.get_cloned::<TreeControl>(&self.control_key);
+        let result = match control {
+            Some(TreeControl::Pause) => {
+                return self.last_result.clone().unwrap_or(NodeResult::Running);
+            },
+            Some(TreeControl::Abort) => {
+                blackboard.remove(&self.control_key);
+                self.root.reset();
+                NodeResult::Failed
+            },
+            Some(TreeControl::Resume) | None => {
+                if self.trace.is_none() && self.stats.is_none() {
+                    self.root.tick(blackboard)
+                } else {
+                    let mut observers = Observers { trace: self.trace.as_mut(), stats: self.stats.as_mut() };
+                    self.root.tick_traced(blackboard, &mut observers)
+                }
+            }
+        };
+        self.last_result = Some(result.clone());
+        result
+    }
+
+    pub fn structure(&self) -> NodeDescription {
+        NodeDescription::of(self.root.as_ref())
+    }
+
+    /// The tree as indented `name (Kind)` lines, one per node, children two
+    /// spaces deeper than their parent.
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        self.structure().render(0, &mut out);
+        out
+    }
+
+    /// Event-driven ticking against the tree's own blackboard: the root is
+    /// only ticked when the blackboard changed since the last call, see
+    /// `BlackBoard::version`; otherwise the last result is returned as is.
+    /// Changes the tree makes while ticking don't count, and time-based
+    /// nodes don't advance while ticks are skipped. Entries whose TTL ran
+    /// out are removed first, so an expiry counts as a change.
+    pub fn tick_if_dirty(&mut self) -> NodeResult {
+        self.blackboard.remove_expired();
+        if let Some(result) = &self.last_result
+            && self.ticked_version == Some(self.blackboard.version()) {
+            return result.clone();
+        }
+        let mut blackboard = std::mem::take(&mut self.blackboard);
+        let result = self.tick(&mut blackboard);
+        self.ticked_version = Some(blackboard.version());
+        self.blackboard = blackboard;
+        result
+    }
+
+    /// Ticks per second over the last `TICK_RATE_WINDOW` calls to `tick`.
+    /// `None` until two ticks at distinct times have been recorded.
+    pub fn tick_rate(&self) -> Option<f64> {
+        let (first, last) = (self.tick_times.front()?, self.tick_times.back()?);
+        let span = last.duration_since(*first).as_secs_f64();
+        if span == 0.0 {
+            return None;
+        }
+        Some((self.tick_times.len() - 1) as f64 / span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::blackboard::BlackBoard;
+    use crate::clock::ManualClock;
+    use crate::node::NodeResult;
+    use crate::nodes::action::Action;
+    use crate::nodes::inverter::Inverter;
+    use crate::nodes::parallel::{Parallel, ParallelPolicy};
+    use crate::nodes::repeat::{Repeat, RepeatMode};
+    use crate::nodes::selector::Selector;
+    use crate::nodes::sequence::Sequence;
+    use crate::nodes::sub_tree::SubTree;
+    use crate::nodes::timed_parallel::TimedParallel;
+    use crate::nodes::timeout::Timeout;
+    use crate::tests::{MockNode, context_at};
+    use crate::tree::{BehaviouralTree, CONTROL_KEY, TICK_RATE_WINDOW, TreeControl};
+
+    fn signal(memory: &mut BlackBoard, control: TreeControl) {
+        memory.data.insert(CONTROL_KEY.into(), Box::new(control));
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let root = MockNode::new("root", vec![NodeResult::Running, NodeResult::Passed]);
+        let probe = root.probe();
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        signal(&mut memory, TreeControl::Pause);
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.ticks(), 1);
+
+        signal(&mut memory, TreeControl::Resume);
+        assert_eq!(tree.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_abort_resets_once() {
+        let root = MockNode::new("root", vec![NodeResult::Running, NodeResult::Passed]);
+        let probe = root.probe();
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        signal(&mut memory, TreeControl::Abort);
+        assert_eq!(tree.tick(&mut memory), NodeResult::Failed);
+        assert_eq!(probe.resets(), 1);
+        assert!(!memory.contains_key(CONTROL_KEY));
+
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        assert_eq!(probe.ticks(), 2);
+    }
+
+    #[test]
+    fn test_tick_rate_follows_spacing() {
+        let clock = ManualClock::new();
+        let root = MockNode::new("root", vec![NodeResult::Running]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new())
+            .with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        tree.tick(&mut memory);
+        assert_eq!(tree.tick_rate(), None);
+
+        for _ in 0..10 {
+            clock.advance(Duration::from_millis(100));
+            tree.tick(&mut memory);
+        }
+        assert!((tree.tick_rate().unwrap() - 10.0).abs() < 1e-9);
+
+        for _ in 0..TICK_RATE_WINDOW {
+            clock.advance(Duration::from_millis(50));
+            tree.tick(&mut memory);
+        }
+        assert_eq!(tree.tick_times.len(), TICK_RATE_WINDOW);
+        assert!((tree.tick_rate().unwrap() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_suspend_and_resume_keep_progress() {
+        let clock = ManualClock::new();
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let slow = MockNode::new("slow", vec![NodeResult::Running, NodeResult::Running, NodeResult::Passed]);
+        let (first_probe, slow_probe) = (first.probe(), slow.probe());
+        let parallel = TimedParallel::new("timed".into(), vec![Box::new(slow)], 1, Duration::from_secs(5))
+            .with_clock(Arc::new(clock.clone()));
+        let root = Sequence::new("root".into(), vec![Box::new(first), Box::new(parallel)]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(3));
+        tree.suspend(&memory);
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        assert_eq!(slow_probe.ticks(), 1);
+
+        tree.resume(&memory);
+        assert_eq!(tree.tick(&mut memory), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(tree.tick(&mut memory), NodeResult::Passed);
+        assert_eq!(slow_probe.ticks(), 3);
+        assert_eq!(slow_probe.resets(), 0);
+        assert_eq!(first_probe.ticks(), 1);
+        assert_eq!(first_probe.resets(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_suspend_and_resume_at_context_time() {
+        let clock = ManualClock::new();
+        let slow = MockNode::new("slow", vec![NodeResult::Running]);
+        let root = Timeout::new("limit".into(), Duration::from_secs(2), Box::new(slow)).with_clock(Arc::new(clock.clone()));
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let mut memory = BlackBoard::new();
+
+        assert_eq!(tree.tick_ctx(&mut memory, &context_at(&clock, 0)), NodeResult::Running);
+        tree.suspend_ctx(&mut memory, &context_at(&clock, 1));
+        tree.resume_ctx(&mut memory, &context_at(&clock, 61));
+        assert_eq!(tree.tick_ctx(&mut memory, &context_at(&clock, 61)), NodeResult::Running);
+        assert_eq!(tree.tick_ctx(&mut memory, &context_at(&clock, 62)), NodeResult::Failed);
+    }
+
+    #[test]
+    fn test_reset_restarts_from_first_child() {
+        let first = MockNode::new("first", vec![NodeResult::Passed]);
+        let second = MockNode::new("second", vec![NodeResult::Running]);
+        let (first_probe, second_probe) = (first.probe(), second.probe());
+        let root = Sequence::new("root".into(), vec![Box::new(first), Box::new(second)]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        tree.blackboard.set("episode", 1_u32);
+        let mut memory = BlackBoard::new();
+
+        tree.tick(&mut memory);
+        tree.tick(&mut memory);
+        assert_eq!(first_probe.ticks(), 1);
+
+        tree.reset();
+        assert_eq!(tree.last_result, None);
+        assert_eq!(second_probe.resets(), 1);
+        tree.tick(&mut memory);
+        assert_eq!(first_probe.ticks(), 2);
+
+        tree.reset_blackboard();
+        assert!(!tree.blackboard.contains_key("episode"));
+    }
+
+    #[test]
+    fn test_describe_sequence_of_selectors() {
+        let passed = |name: &str| Box::new(Action::new(name.into(), |_| NodeResult::Passed));
+        let root = Sequence::new("root".into(), vec![
+            Box::new(Selector::new("find_cover".into(), vec![passed("wall"), passed("crate")])),
+            Box::new(Selector::new("attack".into(), vec![passed("shoot")])),
+        ]);
+        let tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+
+        let structure = tree.structure();
+        assert_eq!(structure.kind, "Sequence");
+        assert_eq!(structure.children[0].children[1].name, "crate");
+        assert_eq!(tree.describe(), concat!(
+            "root (Sequence)\n",
+            "  find_cover (Selector)\n",
+            "    wall (Action)\n",
+            "    crate (Action)\n",
+            "  attack (Selector)\n",
+            "    shoot (Action)\n",
+        ));
+    }
+
+    #[test]
+    fn test_trace_records_execution_path() {
+        let root = Sequence::new("root".into(), vec![
+            Box::new(Action::new("look".into(), |_| NodeResult::Passed)),
+            Box::new(Selector::new("move".into(), vec![
+                Box::new(Inverter::new(Box::new(Action::new("path_clear".into(), |_| NodeResult::Passed)), "blocked".into())),
+                Box::new(Action::new("walk".into(), |_| NodeResult::Running)),
+            ])),
+        ]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let sink = entries.clone();
+        tree.set_trace(Box::new(move |name, result| sink.lock().unwrap().push((name.to_string(), result))));
+
+        assert_eq!(tree.tick(&mut BlackBoard::new()), NodeResult::Running);
+        let entries: Vec<_> = entries.lock().unwrap().iter().map(|(name, result)| (name.clone(), result.clone())).collect();
+        assert_eq!(entries, [
+            ("look".to_string(), NodeResult::Passed),
+            ("path_clear".to_string(), NodeResult::Passed),
+            ("blocked".to_string(), NodeResult::Failed),
+            ("walk".to_string(), NodeResult::Running),
+            ("move".to_string(), NodeResult::Running),
+            ("root".to_string(), NodeResult::Running),
+        ]);
+    }
+
+    #[test]
+    fn test_trace_reaches_through_decorators() {
+        let root = Parallel::new("root".into(), ParallelPolicy::RequireAll, vec![
+            Box::new(Repeat::new("patrol".into(), Box::new(Action::new("walk".into(), |_| NodeResult::Passed)), RepeatMode::Forever)),
+            Box::new(Timeout::new("scan_limit".into(), Duration::from_secs(1), Box::new(
+                SubTree::new("scanner".into(), Vec::new(), Box::new(Action::new("scan".into(), |_| NodeResult::Running)))
+            ))),
+        ]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let sink = entries.clone();
+        tree.set_trace(Box::new(move |name, _| sink.lock().unwrap().push(name.to_string())));
+
+        assert_eq!(tree.tick(&mut BlackBoard::new()), NodeResult::Running);
+        assert_eq!(*entries.lock().unwrap(), ["walk", "patrol", "scan", "scanner", "scan_limit", "root"]);
+    }
+
+    #[test]
+    fn test_tick_if_dirty_skips_unchanged_blackboard() {
+        let root = Action::new("count".into(), |bb| {
+            *bb.entry_or_insert_with("ticks", || 0_u32) += 1;
+            match bb.get::<bool>("done") {
+                Some(true) => NodeResult::Passed,
+                _ => NodeResult::Running
+            }
+        });
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new());
+        let ticks = |tree: &BehaviouralTree| tree.blackboard.get::<u32>("ticks").copied();
+
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Running);
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Running);
+        assert_eq!(ticks(&tree), Some(1));
+
+        tree.blackboard.set("done", true);
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Passed);
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Passed);
+        assert_eq!(ticks(&tree), Some(2));
+
+        tree.reset();
+        tree.tick_if_dirty();
+        assert_eq!(ticks(&tree), Some(3));
+    }
+
+    #[test]
+    fn test_tick_if_dirty_ticks_on_expiry() {
+        let clock = ManualClock::new();
+        let root = Action::new("watch".into(), |bb| match bb.get::<bool>("enemy_seen") {
+            Some(true) => NodeResult::Running,
+            _ => NodeResult::Passed
+        });
+        let mut blackboard = BlackBoard::new().with_clock(Arc::new(clock.clone()));
+        blackboard.set_with_ttl("enemy_seen", true, Duration::from_secs(1));
+        let mut tree = BehaviouralTree::new(Box::new(root), blackboard);
+
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Running);
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Running);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(tree.tick_if_dirty(), NodeResult::Passed);
+    }
+
+    #[test]
+    fn test_stats_count_and_time_ticks() {
+        let clock = ManualClock::new();
+        let work_clock = clock.clone();
+        let mut cost = 0;
+        let work = Action::new("work".into(), move |_| {
+            cost += 1;
+            work_clock.advance(Duration::from_millis(cost));
+            NodeResult::Passed
+        });
+        let work_id = work.id;
+        let root = Sequence::new("root".into(), vec![Box::new(Action::new("look".into(), |_| NodeResult::Passed)), Box::new(work)]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new()).with_clock(Arc::new(clock.clone()));
+        let mut memory = BlackBoard::new();
+
+        tree.tick(&mut memory);
+        assert!(tree.stats().is_none());
+        tree.enable_stats();
+        for _ in 0..3 {
+            tree.tick(&mut memory);
+        }
+
+        let stats = tree.stats().unwrap();
+        let work = stats.get(work_id).unwrap();
+        assert_eq!(work.ticks, 3);
+        assert_eq!(work.total, Duration::from_millis(2 + 3 + 4));
+        assert_eq!((work.min, work.max, work.last), (Duration::from_millis(2), Duration::from_millis(4), Duration::from_millis(4)));
+        assert_eq!(work.mean(), Duration::from_millis(3));
+        assert_eq!(stats.by_name("root")[0].ticks, 3);
+        assert_eq!(stats.by_name("root")[0].total, work.total);
+        assert_eq!(stats.by_name("look")[0].total, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_stats_reach_below_parallel_and_repeat() {
+        let clock = ManualClock::new();
+        let work_clock = clock.clone();
+        let work = Action::new("work".into(), move |_| {
+            work_clock.advance(Duration::from_millis(5));
+            NodeResult::Passed
+        });
+        let work_id = work.id;
+        let root = Parallel::new("root".into(), ParallelPolicy::RequireAll, vec![
+            Box::new(Repeat::new("loop".into(), Box::new(work), RepeatMode::Forever)),
+            Box::new(Action::new("idle".into(), |_| NodeResult::Running)),
+        ]);
+        let mut tree = BehaviouralTree::new(Box::new(root), BlackBoard::new()).with_clock(Arc::new(clock.clone()));
+        tree.enable_stats();
+        let mut memory = BlackBoard::new();
+        for _ in 0..2 {
+            tree.tick(&mut memory);
+        }
+
+        let stats = tree.stats().unwrap();
+        assert_eq!(stats.get(work_id).unwrap().ticks, 2);
+        assert_eq!(stats.get(work_id).unwrap().total, Duration::from_millis(10));
+        assert_eq!(stats.by_name("loop")[0].total, Duration::from_millis(10));
+        assert_eq!(stats.by_name("idle")[0].ticks, 2);
+        assert_eq!(stats.by_name("root")[0].total, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_tick_removes_expired_entries() {
+        let clock = ManualClock::new();
+        let mut memory = BlackBoard::new().with_clock(Arc::new(clock.clone()));
+        memory.set_with_ttl("enemy_seen", true, Duration::from_secs(1));
+        let mut tree = BehaviouralTree::new(Box::new(MockNode::new("idle", vec![NodeResult::Passed])), BlackBoard::new());
+
+        tree.tick(&mut memory);
+        assert!(memory.data.contains_key("enemy_seen"));
+        clock.advance(Duration::from_secs(1));
+        tree.tick(&mut memory);
+        assert!(!memory.data.contains_key("enemy_seen"));
+    }
+}